@@ -1,6 +1,6 @@
 use node_template_runtime::{
-    AccountId, AuraConfig, BalancesConfig, CurrencyId, GenesisConfig, GrandpaConfig,
-    Signature, SudoConfig, SystemConfig, TokensConfig, WASM_BINARY,
+    AccountId, AuraConfig, Balance, BalancesConfig, CurrencyId, ExchangeConfig, GenesisConfig,
+    GrandpaConfig, KittiesModuleConfig, Signature, SudoConfig, SystemConfig, TokensConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -64,6 +64,29 @@ pub fn development_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
                 ],
+                // Demo kitties: a pair for Alice to breed and two of Bob's, one of them for sale
+                vec![
+                    (
+                        get_account_id_from_seed::<sr25519::Public>("Alice"),
+                        [0x00, 0x13, 0x42, 0x07, 0x21, 0x55, 0x10, 0x8e, 0x3a, 0x91, 0x0c, 0x6f, 0xd2, 0x47, 0xb8, 0x19],
+                        None,
+                    ),
+                    (
+                        get_account_id_from_seed::<sr25519::Public>("Alice"),
+                        [0x01, 0x6a, 0x05, 0x93, 0x4c, 0x12, 0xe7, 0x30, 0x5b, 0x28, 0xf1, 0x84, 0x0d, 0x76, 0x2e, 0xc3],
+                        None,
+                    ),
+                    (
+                        get_account_id_from_seed::<sr25519::Public>("Bob"),
+                        [0x02, 0xb4, 0x71, 0x28, 0x96, 0x3d, 0x40, 0xfa, 0x17, 0x6c, 0x85, 0x2b, 0xe9, 0x50, 0x34, 0x0a],
+                        Some((CurrencyId::Native, 10u128.pow(15))),
+                    ),
+                    (
+                        get_account_id_from_seed::<sr25519::Public>("Bob"),
+                        [0x03, 0x2f, 0xc8, 0x5e, 0x01, 0xa7, 0x63, 0x19, 0xd0, 0x4a, 0x3e, 0x92, 0x75, 0x0b, 0xcc, 0x68],
+                        None,
+                    ),
+                ],
                 true,
             )
         },
@@ -114,6 +137,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
                 ],
+                // No kitties
+                vec![],
                 true,
             )
         },
@@ -136,6 +161,7 @@ fn testnet_genesis(
     initial_authorities: Vec<(AuraId, GrandpaId)>,
     root_key: AccountId,
     endowed_accounts: Vec<AccountId>,
+    initial_kitties: Vec<(AccountId, [u8; 16], Option<(CurrencyId, Balance)>)>,
     _enable_println: bool,
 ) -> GenesisConfig {
     GenesisConfig {
@@ -165,6 +191,10 @@ fn testnet_genesis(
             // Assign network admin rights.
             key: root_key,
         }),
+        pallet_kitties: Some(KittiesModuleConfig {
+            kitties: initial_kitties,
+        }),
+        pallet_exchange: Some(ExchangeConfig::default()),
         orml_tokens: Some(TokensConfig {
             endowed_accounts: endowed_accounts
                 .iter()
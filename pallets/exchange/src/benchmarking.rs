@@ -0,0 +1,172 @@
+//! Benchmarks for the exchange pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// The pallet can't name a currency, so use the one encoded as zero (the first variant or id).
+fn currency_id<T: Trait>() -> CurrencyIdOf<T> {
+    CurrencyIdOf::<T>::decode(&mut &[0u8; 32][..]).expect("zero bytes decode to a currency id")
+}
+
+/// A second currency, the one encoded as one.
+fn other_currency_id<T: Trait>() -> CurrencyIdOf<T> {
+    let mut encoded = [0u8; 32];
+    encoded[0] = 1;
+    CurrencyIdOf::<T>::decode(&mut &encoded[..]).expect("a one decodes to a currency id")
+}
+
+fn amount<T: Trait>() -> BalanceOf<T> {
+    1_000_000u32.into()
+}
+
+fn funded<T: Trait>(who: &T::AccountId) {
+    for currency_id in [currency_id::<T>(), other_currency_id::<T>()].iter() {
+        T::Currency::deposit(*currency_id, who, BalanceOf::<T>::max_value() / 1_000u32.into())
+            .expect("deposit to a new account succeeds");
+    }
+}
+
+fn submit<T: Trait>(
+    owner: &T::AccountId,
+    base_currency_id: CurrencyIdOf<T>,
+    base_amount: BalanceOf<T>,
+    target_currency_id: CurrencyIdOf<T>,
+    target_amount: BalanceOf<T>,
+) -> Result<T::OrderId, &'static str> {
+    let order_id = NextOrderId::<T>::get();
+    Module::<T>::submit_order(
+        RawOrigin::Signed(owner.clone()).into(),
+        base_currency_id,
+        base_amount,
+        target_currency_id,
+        target_amount,
+    )?;
+    Ok(order_id)
+}
+
+/// Fill a book with `b` orders of `owner` at the same price, returns the id of the last one.
+fn fill_book<T: Trait>(owner: &T::AccountId, b: u32) -> Result<T::OrderId, &'static str> {
+    let mut order_id = Zero::zero();
+    for _ in 0 .. b {
+        order_id = submit::<T>(owner, currency_id::<T>(), amount::<T>(), other_currency_id::<T>(), amount::<T>())?;
+    }
+    Ok(order_id)
+}
+
+benchmarks! {
+    _ { }
+
+    submit_order {
+        let m in 0 .. T::MaxMatchesPerOrder::get();
+        let b in 0 .. T::MaxOrdersPerBook::get() - T::MaxMatchesPerOrder::get();
+
+        let (sold, bought) = (currency_id::<T>(), other_currency_id::<T>());
+        // `b` orders on both books, asking four for one so that none of them cross
+        for i in 0 .. b {
+            let filler: T::AccountId = account("filler", i, SEED);
+            funded::<T>(&filler);
+            submit::<T>(&filler, sold, amount::<T>(), bought, amount::<T>() * 4u32.into())?;
+            submit::<T>(&filler, bought, amount::<T>(), sold, amount::<T>() * 4u32.into())?;
+        }
+        // Resting orders asking two for one, all taken by the new order
+        for i in 0 .. m {
+            let maker: T::AccountId = account("maker", i, SEED);
+            funded::<T>(&maker);
+            submit::<T>(&maker, bought, amount::<T>(), sold, amount::<T>() * 2u32.into())?;
+        }
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let orders: BalanceOf<T> = (m + 1).into();
+        let order_id = NextOrderId::<T>::get();
+    }: _(RawOrigin::Signed(caller), sold, amount::<T>() * 2u32.into() * orders, bought, amount::<T>() * orders)
+    verify {
+        // Every maker is taken and the rest of the new order is on the book
+        assert!(Orders::<T>::contains_key(order_id));
+        assert_eq!(Module::<T>::order_book((sold, bought)).len() as u32, b + 1);
+        assert_eq!(Module::<T>::order_book((bought, sold)).len() as u32, b);
+    }
+
+    offer_nft {
+        let caller: T::AccountId = whitelisted_caller();
+        let nft_id = T::Nft::mint_for_benchmarks(&caller)?;
+        let order_id = NextOrderId::<T>::get();
+    }: _(RawOrigin::Signed(caller), nft_id, currency_id::<T>(), amount::<T>())
+    verify {
+        assert!(Orders::<T>::contains_key(order_id));
+        assert!(T::Nft::is_locked(nft_id));
+    }
+
+    request_nft {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let nft_id = T::Nft::mint_for_benchmarks(&owner)?;
+        let order_id = NextOrderId::<T>::get();
+    }: _(RawOrigin::Signed(caller), currency_id::<T>(), amount::<T>(), nft_id)
+    verify {
+        assert!(Orders::<T>::contains_key(order_id));
+    }
+
+    take_order {
+        let b in 1 .. T::MaxOrdersPerBook::get();
+
+        let maker: T::AccountId = account("maker", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&maker);
+        funded::<T>(&caller);
+        let order_id = fill_book::<T>(&maker, b)?;
+    }: _(RawOrigin::Signed(caller), order_id, amount::<T>())
+    verify {
+        assert!(!Orders::<T>::contains_key(order_id));
+        assert_eq!(Module::<T>::order_book((currency_id::<T>(), other_currency_id::<T>())).len() as u32, b - 1);
+    }
+
+    take_nft_order {
+        let maker: T::AccountId = account("maker", 0, SEED);
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&maker);
+        funded::<T>(&caller);
+        let nft_id = T::Nft::mint_for_benchmarks(&maker)?;
+        let order_id = NextOrderId::<T>::get();
+        Module::<T>::offer_nft(RawOrigin::Signed(maker).into(), nft_id, currency_id::<T>(), amount::<T>())?;
+    }: take_order(RawOrigin::Signed(caller.clone()), order_id, amount::<T>())
+    verify {
+        assert_eq!(T::Nft::owner(nft_id), Some(caller));
+    }
+
+    cancel_order {
+        let b in 1 .. T::MaxOrdersPerBook::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        funded::<T>(&caller);
+        let order_id = fill_book::<T>(&caller, b)?;
+    }: _(RawOrigin::Signed(caller), order_id)
+    verify {
+        assert!(!Orders::<T>::contains_key(order_id));
+        assert_eq!(Module::<T>::order_book((currency_id::<T>(), other_currency_id::<T>())).len() as u32, b - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_submit_order::<Test>());
+            assert_ok!(test_benchmark_offer_nft::<Test>());
+            assert_ok!(test_benchmark_request_nft::<Test>());
+            assert_ok!(test_benchmark_take_order::<Test>());
+            assert_ok!(test_benchmark_take_nft_order::<Test>());
+            assert_ok!(test_benchmark_cancel_order::<Test>());
+        });
+    }
+}
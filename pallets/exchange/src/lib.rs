@@ -2,16 +2,35 @@
 
 use codec::{Decode, Encode};
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, dispatch, ensure, traits::BalanceStatus,
-    Parameter,
+    decl_error, decl_event, decl_module, decl_storage, dispatch, ensure,
+    traits::{BalanceStatus, Get},
+    weights::Weight, Parameter,
 };
 use frame_system::ensure_signed;
 use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use orml_utilities::with_transaction_result;
+use nft_traits::NonFungibleAsset;
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, MaybeSerializeDeserialize, One, Zero},
-    DispatchResult, RuntimeDebug,
+    helpers_128bit::multiply_by_rational,
+    traits::{
+        AtLeast32BitUnsigned, Bounded, CheckedAdd, MaybeSerializeDeserialize, One,
+        SaturatedConversion, Saturating, Zero,
+    },
+    DispatchError, DispatchResult, Rational128, RuntimeDebug,
 };
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod migration;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
 
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
@@ -24,19 +43,76 @@ pub trait Trait: frame_system::Trait {
         + Copy
         + MaybeSerializeDeserialize
         + Bounded;
+    /// Non-fungible assets that can be traded against a currency.
+    type Nft: NonFungibleAsset<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+    /// Most resting orders a new order is matched against, the rest of it goes on the book.
+    type MaxMatchesPerOrder: Get<u32>;
+    /// Most orders in the book of a currency pair, orders that would rest on a full book are rejected.
+    type MaxOrdersPerBook: Get<u32>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+/// Layout versions of the pallet storage, `None` is the original one.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+    /// Each side of an `Order` is an `OrderAsset`.
+    V1,
+    /// `Order` records how much of its target is filled.
+    V2,
+    /// Open currency orders are kept in `OrderBook`.
+    V3,
 }
 
+/// One side of an order.
 #[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
-pub struct Order<CurrencyId, Balance, AccountId> {
-    pub base_currency_id: CurrencyId,
-    #[codec(compact)]
-    pub base_amount: Balance,
-    pub target_currency_id: CurrencyId,
+pub enum OrderAsset<CurrencyId, Balance, NftId> {
+    /// An amount of a currency.
+    Currency(CurrencyId, #[codec(compact)] Balance),
+    /// A single non-fungible asset, such as a kitty.
+    Nft(NftId),
+}
+
+impl<CurrencyId, Balance: Copy + One, NftId> OrderAsset<CurrencyId, Balance, NftId> {
+    /// Units of the asset, an NFT is a single unit.
+    pub fn amount(&self) -> Balance {
+        match self {
+            OrderAsset::Currency(_, amount) => *amount,
+            OrderAsset::Nft(_) => One::one(),
+        }
+    }
+}
+
+/// An open order. The unfilled part of the owner's `base` is held in escrow until the order is
+/// taken or cancelled.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
+pub struct Order<CurrencyId, Balance, NftId, AccountId> {
+    /// What the owner gives.
+    pub base: OrderAsset<CurrencyId, Balance, NftId>,
+    /// What the owner wants in return.
+    pub target: OrderAsset<CurrencyId, Balance, NftId>,
+    /// Part of the target paid by takers so far.
     #[codec(compact)]
-    pub target_amount: Balance,
+    pub filled: Balance,
     pub owner: AccountId,
 }
 
+impl<CurrencyId: Copy, Balance, NftId, AccountId> Order<CurrencyId, Balance, NftId, AccountId> {
+    /// `(base, target)` currencies of an order with no NFT side, the key of its `OrderBook`.
+    pub fn currency_pair(&self) -> Option<(CurrencyId, CurrencyId)> {
+        match (&self.base, &self.target) {
+            (OrderAsset::Currency(base, _), OrderAsset::Currency(target, _)) => Some((*base, *target)),
+            _ => None,
+        }
+    }
+
+    /// Orders with an NFT on either side can only be filled whole.
+    pub fn is_divisible(&self) -> bool {
+        self.currency_pair().is_some()
+    }
+}
+
 //<<T as Trait>::Currency as MultiCurrency<<T as frame_system::Trait>::AccountId>>::Balance
 // 限制泛型参数T， 必须是一个Trait
 // 			   ::Currency   => 拿到currency
@@ -48,13 +124,25 @@ type BalanceOf<T> =
 
 type CurrencyIdOf<T> =
     <<T as Trait>::Currency as MultiCurrency<<T as frame_system::Trait>::AccountId>>::CurrencyId;
-// Order有三个泛型参数
-type OrderOf<T> = Order<CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Trait>::AccountId>;
+type NftIdOf<T> = <<T as Trait>::Nft as NonFungibleAsset<
+    <T as frame_system::Trait>::AccountId,
+    CurrencyIdOf<T>,
+    BalanceOf<T>,
+>>::AssetId;
+
+type OrderAssetOf<T> = OrderAsset<CurrencyIdOf<T>, BalanceOf<T>, NftIdOf<T>>;
+// Order有四个泛型参数
+type OrderOf<T> = Order<CurrencyIdOf<T>, BalanceOf<T>, NftIdOf<T>, <T as frame_system::Trait>::AccountId>;
 
 decl_storage! {
     trait  Store for Module<T: Trait> as Exchange {
         pub Orders: map hasher(twox_64_concat) T::OrderId => Option<OrderOf<T>>;
         pub NextOrderId: T::OrderId;
+        // Open currency orders by `(base, target)` pair as `(base_amount, target_amount, order_id)`,
+        // sorted by exact price (target per base) and then by id, so that the best and oldest
+        // order comes first. Holds at most `MaxOrdersPerBook` orders.
+        pub OrderBook get(fn order_book): map hasher(blake2_128_concat) (CurrencyIdOf<T>, CurrencyIdOf<T>) => Vec<(BalanceOf<T>, BalanceOf<T>, T::OrderId)>;
+        StorageVersion build(|_| Some(Releases::V3)): Option<Releases>;
     }
 }
 
@@ -63,6 +151,7 @@ decl_event!(
     where
         AccountId = <T as frame_system::Trait>::AccountId,
         <T as Trait>::OrderId,
+        Balance = BalanceOf<T>,
         Order = OrderOf<T>
     {
         /// Event documentation should end with an array that provides descriptive names for event
@@ -71,7 +160,12 @@ decl_event!(
 
         OrderCreated(OrderId, Order),
         OrderTaken(AccountId, OrderId, Order),
+        /// Part of an order was filled and the rest stays open. [taker, order_id, paid, received]
+        OrderPartiallyFilled(AccountId, OrderId, Balance, Balance),
         OrderCancelled(OrderId),
+        /// A new order was matched against a resting one on the book.
+        /// [order_id, resting_order_id, paid, received]
+        OrdersMatched(OrderId, OrderId, Balance, Balance),
     }
 );
 
@@ -82,6 +176,12 @@ decl_error! {
         InsufficientBalance,
         NotOwner,
 		InvalidOrderId,
+        InvalidNftId,
+        ZeroAmount,
+        OrderNotDivisible,
+        FillTooSmall,
+        SameCurrency,
+        OrderBookFull,
     }
 }
 
@@ -97,72 +197,411 @@ decl_module! {
         fn deposit_event() = default;
 
 
-		#[weight = 1000]
+        /// Trade `base_amount` for at least `target_amount`. The order is first matched against
+        /// the best priced orders going the other way, at their prices, and what is left of it
+        /// goes on the book at its own price.
+		#[weight = T::WeightInfo::submit_order(T::MaxMatchesPerOrder::get(), T::MaxOrdersPerBook::get())]
         fn submit_order(
             origin,
             base_currency_id: CurrencyIdOf<T>,
             base_amount: BalanceOf<T>,
             target_currency_id: CurrencyIdOf<T>,
             target_amount: BalanceOf<T>,
-		 ) {
+		 ) -> dispatch::DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure!(!base_amount.is_zero() && !target_amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(base_currency_id != target_currency_id, Error::<T>::SameCurrency);
 
-            NextOrderId::<T>::try_mutate(|id| -> DispatchResult{
-                let order_id = *id;
-                let order = Order {
-                    base_currency_id,
-                    base_amount,
-                    target_currency_id,
-                    target_amount,
-                    owner: who.clone(),
-                };
-                *id = id.checked_add(&One::one()).ok_or(Error::<T>::OrderIdOverFlow)?;
-
-                T::Currency::reserve(base_currency_id, &who, base_amount)?;
-                Orders::<T>::insert(order_id, &order);
-
-                Self::deposit_event(RawEvent::OrderCreated(order_id, order));
+            let mut weight = 0;
+            with_transaction_result(|| {
+                let (matches, book_len) = Self::match_order(who, base_currency_id, base_amount, target_currency_id, target_amount)?;
+                weight = T::WeightInfo::submit_order(matches, book_len);
                 Ok(())
             })?;
+
+            Ok(Some(weight).into())
         }
 
-        #[weight = 1000]
-        fn take_order(origin, order_id: T::OrderId){
+        /// Offer an NFT for an amount of a currency, the NFT is locked until the order is
+        /// taken or cancelled.
+        #[weight = T::WeightInfo::offer_nft()]
+        fn offer_nft(
+            origin,
+            nft_id: NftIdOf<T>,
+            target_currency_id: CurrencyIdOf<T>,
+            target_amount: BalanceOf<T>,
+        ) {
+            let who = ensure_signed(origin)?;
+
+            Self::place_order(
+                who,
+                OrderAsset::Nft(nft_id),
+                OrderAsset::Currency(target_currency_id, target_amount),
+            )?;
+        }
+
+        /// Bid an amount of a currency for an NFT, whoever owns the NFT can take the order.
+        #[weight = T::WeightInfo::request_nft()]
+        fn request_nft(
+            origin,
+            base_currency_id: CurrencyIdOf<T>,
+            base_amount: BalanceOf<T>,
+            nft_id: NftIdOf<T>,
+        ) {
+            let who = ensure_signed(origin)?;
+            ensure!(T::Nft::owner(nft_id).is_some(), Error::<T>::InvalidNftId);
+
+            Self::place_order(
+                who,
+                OrderAsset::Currency(base_currency_id, base_amount),
+                OrderAsset::Nft(nft_id),
+            )?;
+        }
+
+        /// Pay `amount` of the target of an order, capped at what is left of it, for the matching
+        /// part of its base. The order stays open until its whole target is paid.
+        #[weight = T::WeightInfo::take_order(T::MaxOrdersPerBook::get()).max(T::WeightInfo::take_nft_order())]
+        fn take_order(origin, order_id: T::OrderId, amount: BalanceOf<T>) -> dispatch::DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             // take and delete from ma				order is option type
-            Orders::<T>::try_mutate_exists(order_id, |order| -> DispatchResult {
+            // Length of the book the order was removed from, `None` for NFT orders
+            let book_len = Orders::<T>::try_mutate_exists(order_id, |maybe_order| -> Result<Option<u32>, DispatchError> {
                 //				take from option
-                let order = order.take().ok_or(Error::<T>::InvalidOrderId)?;
+                let mut order = maybe_order.take().ok_or(Error::<T>::InvalidOrderId)?;
+
+                let remaining = order.target.amount().saturating_sub(order.filled);
+                let fill = amount.min(remaining);
+                ensure!(!fill.is_zero(), Error::<T>::ZeroAmount);
+                ensure!(fill == remaining || order.is_divisible(), Error::<T>::OrderNotDivisible);
 
+                let filled = order.filled.saturating_add(fill);
+                let payout = Self::base_for(&order, filled).saturating_sub(Self::base_for(&order, order.filled));
+                ensure!(!payout.is_zero(), Error::<T>::FillTooSmall);
+
+                let divisible = order.is_divisible();
+                let mut book_len = 0;
                 with_transaction_result(|| {
-                    T::Currency::transfer(order.target_currency_id, &who, &order.owner, order.target_amount)?;
-                    let val = T::Currency::repatriate_reserved(order.base_currency_id, &order.owner, &who, order.base_amount, BalanceStatus::Free)?;
-                    ensure!(val.is_zero(), Error::<T>::InsufficientBalance);
+                    Self::pay(&who, &order.owner, &order.target, fill, &order.base)?;
+                    Self::deliver(&order.owner, &who, &order.base, payout, &order.target)?;
+                    order.filled = filled;
 
-                    Self::deposit_event(RawEvent::OrderTaken(who, order_id, order));
+                    if fill == remaining {
+                        book_len = Self::remove_from_book(order_id, &order);
+                        Self::deposit_event(RawEvent::OrderTaken(who, order_id, order));
+                    } else {
+                        Self::deposit_event(RawEvent::OrderPartiallyFilled(who, order_id, fill, payout));
+                        *maybe_order = Some(order);
+                    }
 
                     Ok(())
-                })
+                })?;
 
+                Ok(if divisible { Some(book_len) } else { None })
             })?;
+
+            match book_len {
+                Some(book_len) => Ok(Some(T::WeightInfo::take_order(book_len)).into()),
+                None => Ok(Some(T::WeightInfo::take_nft_order()).into()),
+            }
 		}
 
-		#[weight = 1000]
-        fn cancel_order(origin, order_id: T::OrderId) {
+		#[weight = T::WeightInfo::cancel_order(T::MaxOrdersPerBook::get())]
+        fn cancel_order(origin, order_id: T::OrderId) -> dispatch::DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            Orders::<T>::try_mutate_exists(order_id, |order| -> DispatchResult{
+            let book_len = Orders::<T>::try_mutate_exists(order_id, |order| -> Result<u32, DispatchError> {
                 let order = order.take().ok_or(Error::<T>::InvalidOrderId)?;
 
                 ensure!(order.owner == who, Error::<T>::NotOwner);
+                let unfilled = order.base.amount().saturating_sub(Self::base_for(&order, order.filled));
+                Self::release(&order.owner, &order.base, unfilled)?;
+                let book_len = Self::remove_from_book(order_id, &order);
 
                 Self::deposit_event(RawEvent::OrderCancelled(order_id));
 
-				Ok(())
+				Ok(book_len)
             })?;
+
+            Ok(Some(T::WeightInfo::cancel_order(book_len)).into())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migration::migrate_to_v1::<T>()
+                .saturating_add(migration::migrate_to_v2::<T>())
+                .saturating_add(migration::migrate_to_v3::<T>())
         }
+    }
+}
 
+impl<T: Trait> Module<T> {
+    fn place_order(owner: T::AccountId, base: OrderAssetOf<T>, target: OrderAssetOf<T>) -> DispatchResult {
+        NextOrderId::<T>::try_mutate(|id| -> DispatchResult {
+            let order_id = *id;
+            *id = id.checked_add(&One::one()).ok_or(Error::<T>::OrderIdOverFlow)?;
 
+            ensure!(!base.amount().is_zero() && !target.amount().is_zero(), Error::<T>::ZeroAmount);
+            Self::escrow(&owner, &base)?;
+            let order = Order { base, target, filled: Zero::zero(), owner };
+            Orders::<T>::insert(order_id, &order);
+
+            Self::deposit_event(RawEvent::OrderCreated(order_id, order));
+            Ok(())
+        })
+    }
+
+    fn next_order_id() -> Result<T::OrderId, DispatchError> {
+        NextOrderId::<T>::try_mutate(|id| {
+            let order_id = *id;
+            *id = id.checked_add(&One::one()).ok_or(Error::<T>::OrderIdOverFlow)?;
+            Ok(order_id)
+        })
+    }
+
+    /// Price of an order on the book: target asked per unit of base. Prices compare exactly, by
+    /// their cross products.
+    fn price(base_amount: BalanceOf<T>, target_amount: BalanceOf<T>) -> Rational128 {
+        Rational128::from(target_amount.saturated_into(), base_amount.saturated_into())
+    }
+
+    /// Match a new currency order against the book of the opposite pair, best price and then
+    /// oldest first, for at most `MaxMatchesPerOrder` resting orders. Each match is at the
+    /// resting order's price, rounded in its favour like `take_order`. What is left rests on the
+    /// book at the new order's own price and anything it saved is unreserved. Returns the
+    /// number of matches and the length of the longest book read.
+    fn match_order(
+        owner: T::AccountId,
+        base_currency_id: CurrencyIdOf<T>,
+        base_amount: BalanceOf<T>,
+        target_currency_id: CurrencyIdOf<T>,
+        target_amount: BalanceOf<T>,
+    ) -> Result<(u32, u32), DispatchError> {
+        let order_id = Self::next_order_id()?;
+        T::Currency::reserve(base_currency_id, &owner, base_amount)?;
+
+        // Resting orders sell our target for our base, so their price is in base per target
+        let limit = Self::price(target_amount, base_amount);
+        let opposite = (target_currency_id, base_currency_id);
+        let mut book = OrderBook::<T>::get(opposite);
+        let mut book_len = book.len() as u32;
+
+        let mut spent = BalanceOf::<T>::zero();
+        let mut received = BalanceOf::<T>::zero();
+        let mut matches = 0;
+        let mut closed = 0;
+        for (resting_base, resting_target, resting_id) in book.iter() {
+            if matches >= T::MaxMatchesPerOrder::get()
+                || spent >= base_amount
+                || received >= target_amount
+                || Self::price(*resting_base, *resting_target) > limit
+            {
+                break;
+            }
+            let mut resting = match Orders::<T>::get(resting_id) {
+                Some(resting) => resting,
+                None => {
+                    closed += 1;
+                    continue;
+                }
+            };
+
+            // Pay the resting order enough to get what we still want, without going over
+            // what it still wants or what we have left
+            let wanted = target_amount.saturating_sub(received);
+            let mut fill = multiply_by_rational(
+                wanted.saturated_into(),
+                resting.target.amount().saturated_into(),
+                resting.base.amount().saturated_into(),
+            )
+            .map(|fill| fill.saturated_into())
+            .unwrap_or_else(|_| BalanceOf::<T>::max_value());
+            if Self::base_for(&resting, resting.filled.saturating_add(fill))
+                .saturating_sub(Self::base_for(&resting, resting.filled)) < wanted
+            {
+                fill = fill.saturating_add(One::one());
+            }
+            let remaining = resting.target.amount().saturating_sub(resting.filled);
+            let fill = fill.min(remaining).min(base_amount - spent);
+
+            let filled = resting.filled.saturating_add(fill);
+            let payout = Self::base_for(&resting, filled).saturating_sub(Self::base_for(&resting, resting.filled));
+            if payout.is_zero() {
+                break;
+            }
+
+            let unpaid = T::Currency::repatriate_reserved(base_currency_id, &owner, &resting.owner, fill, BalanceStatus::Free)?;
+            ensure!(unpaid.is_zero(), Error::<T>::InsufficientBalance);
+            Self::deliver(&resting.owner, &owner, &resting.base, payout, &resting.target)?;
+            resting.filled = filled;
+            spent = spent.saturating_add(fill);
+            received = received.saturating_add(payout);
+            matches += 1;
+
+            Self::deposit_event(RawEvent::OrdersMatched(order_id, *resting_id, fill, payout));
+            if fill == remaining {
+                Orders::<T>::remove(resting_id);
+                closed += 1;
+                Self::deposit_event(RawEvent::OrderTaken(owner.clone(), *resting_id, resting));
+            } else {
+                Orders::<T>::insert(resting_id, resting);
+            }
+        }
+
+        // Only the last matched order can be left open, so the closed ones are at the front
+        book.drain(..closed);
+        if book.is_empty() {
+            OrderBook::<T>::remove(opposite);
+        } else {
+            OrderBook::<T>::insert(opposite, book);
+        }
+
+        // The rest keeps the original price, rounded down in the owner's favour
+        let left = base_amount - spent;
+        let rest_target = target_amount.saturating_sub(received);
+        let rest_base = multiply_by_rational(
+            base_amount.saturated_into(),
+            rest_target.saturated_into(),
+            target_amount.saturated_into(),
+        )
+        .map(|rest_base| rest_base.saturated_into())
+        .unwrap_or_else(|_| BalanceOf::<T>::zero())
+        .min(left);
+        T::Currency::unreserve(base_currency_id, &owner, left - rest_base);
+
+        if !rest_base.is_zero() && !rest_target.is_zero() {
+            let order = Order {
+                base: OrderAsset::Currency(base_currency_id, rest_base),
+                target: OrderAsset::Currency(target_currency_id, rest_target),
+                filled: Zero::zero(),
+                owner,
+            };
+            Orders::<T>::insert(order_id, &order);
+            book_len = book_len.max(Self::add_to_book(order_id, &order)?);
+            Self::deposit_event(RawEvent::OrderCreated(order_id, order));
+        } else {
+            T::Currency::unreserve(base_currency_id, &owner, rest_base);
+        }
+
+        Ok((matches, book_len))
+    }
+
+    /// Insert a currency order in its book after the orders with a better or equal price.
+    /// Fails if the book is full, returns its length before otherwise.
+    fn add_to_book(order_id: T::OrderId, order: &OrderOf<T>) -> Result<u32, DispatchError> {
+        let pair = match order.currency_pair() {
+            Some(pair) => pair,
+            None => return Ok(0),
+        };
+        OrderBook::<T>::try_mutate(pair, |book| {
+            let book_len = book.len() as u32;
+            ensure!(book_len < T::MaxOrdersPerBook::get(), Error::<T>::OrderBookFull);
+
+            let (base, target) = (order.base.amount(), order.target.amount());
+            let price = Self::price(base, target);
+            let index = book
+                .binary_search_by(|(other_base, other_target, other_id)| {
+                    Self::price(*other_base, *other_target).cmp(&price).then(other_id.cmp(&order_id))
+                })
+                .unwrap_or_else(|index| index);
+            book.insert(index, (base, target, order_id));
+            Ok(book_len)
+        })
+    }
+
+    /// Take an order off its book, returns the length of the book before.
+    fn remove_from_book(order_id: T::OrderId, order: &OrderOf<T>) -> u32 {
+        let pair = match order.currency_pair() {
+            Some(pair) => pair,
+            None => return 0,
+        };
+        OrderBook::<T>::mutate_exists(pair, |book| {
+            let entries = match book.as_mut() {
+                Some(entries) => entries,
+                None => return 0,
+            };
+            let book_len = entries.len() as u32;
+            entries.retain(|(_, _, id)| *id != order_id);
+            if entries.is_empty() {
+                *book = None;
+            }
+            book_len
+        })
+    }
+
+    /// Hold the base of a new order: reserve the amount or lock the NFT.
+    fn escrow(owner: &T::AccountId, asset: &OrderAssetOf<T>) -> DispatchResult {
+        match asset {
+            OrderAsset::Currency(currency_id, amount) => T::Currency::reserve(*currency_id, owner, *amount),
+            OrderAsset::Nft(nft_id) => T::Nft::lock(owner, *nft_id),
+        }
+    }
+
+    /// Base paid out for `filled` of the target of an order. It is rounded down, so that the owner
+    /// never gives more than the order price for what they received, and the whole target pays
+    /// out the whole base.
+    fn base_for(order: &OrderOf<T>, filled: BalanceOf<T>) -> BalanceOf<T> {
+        let base = order.base.amount();
+        let target = order.target.amount();
+        if filled >= target {
+            return base;
+        }
+        multiply_by_rational(base.saturated_into(), filled.saturated_into(), target.saturated_into())
+            .map(|amount| amount.saturated_into())
+            .unwrap_or_else(|_| Zero::zero())
+    }
+
+    /// Give `amount` of the escrowed base of a cancelled order back to its owner.
+    fn release(owner: &T::AccountId, asset: &OrderAssetOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+        match asset {
+            OrderAsset::Currency(currency_id, _) => {
+                T::Currency::unreserve(*currency_id, owner, amount);
+                Ok(())
+            }
+            OrderAsset::Nft(nft_id) => T::Nft::unlock(*nft_id),
+        }
+    }
+
+    /// Hand `amount` of the escrowed base of an order from its owner to the taker, who pays
+    /// with the `counter` asset.
+    fn deliver(
+        owner: &T::AccountId,
+        taker: &T::AccountId,
+        asset: &OrderAssetOf<T>,
+        amount: BalanceOf<T>,
+        counter: &OrderAssetOf<T>,
+    ) -> DispatchResult {
+        match asset {
+            OrderAsset::Currency(currency_id, _) => {
+                let unpaid = T::Currency::repatriate_reserved(*currency_id, owner, taker, amount, BalanceStatus::Free)?;
+                ensure!(unpaid.is_zero(), Error::<T>::InsufficientBalance);
+                Ok(())
+            }
+            OrderAsset::Nft(nft_id) => {
+                T::Nft::unlock(*nft_id)?;
+                T::Nft::transfer(owner, taker, *nft_id, Self::trade_price(counter))
+            }
+        }
+    }
+
+    /// Pay `amount` of the target of an order from the taker's free balance or NFTs, for the
+    /// `counter` asset.
+    fn pay(
+        taker: &T::AccountId,
+        owner: &T::AccountId,
+        asset: &OrderAssetOf<T>,
+        amount: BalanceOf<T>,
+        counter: &OrderAssetOf<T>,
+    ) -> DispatchResult {
+        match asset {
+            OrderAsset::Currency(currency_id, _) => T::Currency::transfer(*currency_id, taker, owner, amount),
+            OrderAsset::Nft(nft_id) => T::Nft::transfer(taker, owner, *nft_id, Self::trade_price(counter)),
+        }
+    }
+
+    /// Price an NFT changes hands for when traded against `counter`.
+    fn trade_price(counter: &OrderAssetOf<T>) -> Option<(CurrencyIdOf<T>, BalanceOf<T>)> {
+        match counter {
+            OrderAsset::Currency(currency_id, amount) => Some((*currency_id, *amount)),
+            OrderAsset::Nft(_) => None,
+        }
     }
 }
-impl<T: Trait> Module<T> {}
@@ -0,0 +1,97 @@
+//! Storage migrations of the exchange pallet, run from `on_runtime_upgrade`.
+
+use super::*;
+use frame_support::{
+    storage::migration::{put_storage_value, StorageIterator},
+    IterableStorageMap,
+};
+use sp_std::prelude::*;
+
+/// `Order` before either side could be an NFT.
+#[derive(Decode)]
+struct OrderV0<CurrencyId, Balance, AccountId> {
+    base_currency_id: CurrencyId,
+    #[codec(compact)]
+    base_amount: Balance,
+    target_currency_id: CurrencyId,
+    #[codec(compact)]
+    target_amount: Balance,
+    owner: AccountId,
+}
+
+/// `Order` before partial fills.
+#[derive(Encode, Decode)]
+struct OrderV1<CurrencyId, Balance, NftId, AccountId> {
+    base: OrderAsset<CurrencyId, Balance, NftId>,
+    target: OrderAsset<CurrencyId, Balance, NftId>,
+    owner: AccountId,
+}
+
+type OrderV0Of<T> = OrderV0<CurrencyIdOf<T>, BalanceOf<T>, <T as frame_system::Trait>::AccountId>;
+type OrderV1Of<T> = OrderV1<CurrencyIdOf<T>, BalanceOf<T>, NftIdOf<T>, <T as frame_system::Trait>::AccountId>;
+
+/// Turn both sides of every open order into `OrderAsset::Currency`.
+pub fn migrate_to_v1<T: Trait>() -> Weight {
+    if StorageVersion::get().is_some() {
+        return 0;
+    }
+
+    // Orders are written in the V1 layout, which `migrate_to_v2` then brings up to date
+    let orders = StorageIterator::<OrderV0Of<T>>::new(b"Exchange", b"Orders")
+        .drain()
+        .collect::<Vec<_>>();
+    let count = orders.len() as Weight;
+
+    for (key, old) in orders {
+        let order: OrderV1Of<T> = OrderV1 {
+            base: OrderAsset::Currency(old.base_currency_id, old.base_amount),
+            target: OrderAsset::Currency(old.target_currency_id, old.target_amount),
+            owner: old.owner,
+        };
+        put_storage_value(b"Exchange", b"Orders", &key, order);
+    }
+
+    StorageVersion::put(Releases::V1);
+
+    T::DbWeight::get().reads_writes(count + 1, 2 * count + 1)
+}
+
+/// Mark every open order as not filled at all.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    if StorageVersion::get() != Some(Releases::V1) {
+        return 0;
+    }
+
+    let mut count: Weight = 0;
+    Orders::<T>::translate::<OrderV1Of<T>, _>(|_, old| {
+        count += 1;
+        Some(Order {
+            base: old.base,
+            target: old.target,
+            filled: Zero::zero(),
+            owner: old.owner,
+        })
+    });
+
+    StorageVersion::put(Releases::V2);
+
+    T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
+
+/// Put open currency orders on the book while it has room. Orders that don't fit stay open and
+/// can still be taken or cancelled.
+pub fn migrate_to_v3<T: Trait>() -> Weight {
+    if StorageVersion::get() != Some(Releases::V2) {
+        return 0;
+    }
+
+    let mut count: Weight = 0;
+    for (order_id, order) in Orders::<T>::iter() {
+        let _ = Module::<T>::add_to_book(order_id, &order);
+        count += 1;
+    }
+
+    StorageVersion::put(Releases::V3);
+
+    T::DbWeight::get().reads_writes(2 * count + 1, count + 1)
+}
@@ -0,0 +1,187 @@
+use crate::{Module, Trait};
+use codec::Encode;
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, storage::unhashed, weights::Weight};
+use frame_system as system;
+use nft_traits::NonFungibleAsset;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, DispatchResult, Perbill,
+};
+
+mod exchange {
+    pub use crate::Event;
+}
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        system<T>,
+        orml_tokens<T>,
+        exchange<T>,
+    }
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = TestEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+pub type CurrencyId = u32;
+pub const DOT: CurrencyId = 0;
+pub const BTC: CurrencyId = 1;
+
+impl orml_tokens::Trait for Test {
+    type Event = TestEvent;
+    type Balance = u64;
+    type Amount = i64;
+    type CurrencyId = CurrencyId;
+    type OnReceived = ();
+    type WeightInfo = ();
+}
+
+pub type NftId = u32;
+
+// NFT registry kept in plain storage, so that it is rolled back with failed transactions
+fn nft_key(kind: &[u8], nft_id: NftId) -> Vec<u8> {
+    (b"MockNft", kind, nft_id).encode()
+}
+
+/// NFTs owned by accounts, recording every trade they change hands in.
+pub struct MockNft;
+
+impl MockNft {
+    pub fn mint(owner: u64, nft_id: NftId) {
+        unhashed::put(&nft_key(b"owner", nft_id), &owner);
+    }
+
+    /// `(from, to, price)` of the trades of an NFT, oldest first.
+    pub fn trades(nft_id: NftId) -> Vec<(u64, u64, Option<(CurrencyId, u64)>)> {
+        unhashed::get_or_default(&nft_key(b"trades", nft_id))
+    }
+}
+
+impl NonFungibleAsset<u64, CurrencyId, u64> for MockNft {
+    type AssetId = NftId;
+
+    fn owner(nft_id: NftId) -> Option<u64> {
+        unhashed::get(&nft_key(b"owner", nft_id))
+    }
+
+    fn is_locked(nft_id: NftId) -> bool {
+        unhashed::get_or_default(&nft_key(b"locked", nft_id))
+    }
+
+    fn lock(who: &u64, nft_id: NftId) -> DispatchResult {
+        if Self::owner(nft_id) != Some(*who) {
+            return Err(DispatchError::Other("not the owner"));
+        }
+        if Self::is_locked(nft_id) {
+            return Err(DispatchError::Other("locked"));
+        }
+        unhashed::put(&nft_key(b"locked", nft_id), &true);
+        Ok(())
+    }
+
+    fn unlock(nft_id: NftId) -> DispatchResult {
+        if !Self::is_locked(nft_id) {
+            return Err(DispatchError::Other("not locked"));
+        }
+        unhashed::kill(&nft_key(b"locked", nft_id));
+        Ok(())
+    }
+
+    fn transfer(from: &u64, to: &u64, nft_id: NftId, price: Option<(CurrencyId, u64)>) -> DispatchResult {
+        if Self::owner(nft_id) != Some(*from) {
+            return Err(DispatchError::Other("not the owner"));
+        }
+        if Self::is_locked(nft_id) {
+            return Err(DispatchError::Other("locked"));
+        }
+        Self::mint(*to, nft_id);
+        let mut trades = Self::trades(nft_id);
+        trades.push((*from, *to, price));
+        unhashed::put(&nft_key(b"trades", nft_id), &trades);
+        Ok(())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn mint_for_benchmarks(owner: &u64) -> Result<NftId, DispatchError> {
+        let nft_id = unhashed::get_or_default(b"MockNft:next");
+        unhashed::put(b"MockNft:next", &(nft_id + 1));
+        Self::mint(*owner, nft_id);
+        Ok(nft_id)
+    }
+}
+
+parameter_types! {
+    pub const MaxMatchesPerOrder: u32 = 3;
+    pub const MaxOrdersPerBook: u32 = 8;
+}
+
+impl Trait for Test {
+    type Event = TestEvent;
+    type Currency = orml_tokens::Module<Test>;
+    type OrderId = u32;
+    type Nft = MockNft;
+    type MaxMatchesPerOrder = MaxMatchesPerOrder;
+    type MaxOrdersPerBook = MaxOrdersPerBook;
+    type WeightInfo = ();
+}
+
+pub type Exchange = Module<Test>;
+pub type System = frame_system::Module<Test>;
+pub type Tokens = orml_tokens::Module<Test>;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    orml_tokens::GenesisConfig::<Test> {
+        endowed_accounts: (1..=4).flat_map(|who| vec![(who, DOT, 1_000_000), (who, BTC, 1_000_000)]).collect(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
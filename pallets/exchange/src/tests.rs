@@ -0,0 +1,362 @@
+use crate::{mock::*, Error, Event, Order, OrderAsset, Orders, WeightInfo};
+use frame_support::{assert_noop, assert_ok, StorageMap};
+use nft_traits::NonFungibleAsset;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+
+fn last_event() -> TestEvent {
+    System::events().last().unwrap().event.clone()
+}
+
+#[test]
+fn offer_nft_locks_the_nft() {
+    new_test_ext().execute_with(|| {
+        MockNft::mint(1, 7);
+
+        assert_noop!(Exchange::offer_nft(Origin::signed(2), 7, DOT, 500), "not the owner");
+        assert_noop!(Exchange::offer_nft(Origin::signed(1), 7, DOT, 0), Error::<Test>::ZeroAmount);
+        assert_ok!(Exchange::offer_nft(Origin::signed(1), 7, DOT, 500));
+
+        let order = Order { base: OrderAsset::Nft(7), target: OrderAsset::Currency(DOT, 500), filled: 0, owner: 1 };
+        assert_eq!(Orders::<Test>::get(0), Some(order.clone()));
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderCreated(0, order)));
+        assert!(MockNft::is_locked(7));
+        assert_eq!(MockNft::owner(7), Some(1));
+
+        // A locked NFT can't be offered twice
+        assert_noop!(Exchange::offer_nft(Origin::signed(1), 7, BTC, 500), "locked");
+    })
+}
+
+#[test]
+fn take_nft_offer_records_the_price() {
+    new_test_ext().execute_with(|| {
+        MockNft::mint(1, 7);
+        assert_ok!(Exchange::offer_nft(Origin::signed(1), 7, DOT, 500));
+
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 500));
+
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(MockNft::owner(7), Some(2));
+        assert!(!MockNft::is_locked(7));
+        assert_eq!(MockNft::trades(7), vec![(1, 2, Some((DOT, 500)))]);
+        assert_eq!(Tokens::free_balance(DOT, &1), 1_000_500);
+        assert_eq!(Tokens::free_balance(DOT, &2), 999_500);
+    })
+}
+
+#[test]
+fn request_nft_is_taken_by_the_nft_owner() {
+    new_test_ext().execute_with(|| {
+        MockNft::mint(1, 7);
+
+        assert_noop!(Exchange::request_nft(Origin::signed(2), BTC, 300, 8), Error::<Test>::InvalidNftId);
+        assert_ok!(Exchange::request_nft(Origin::signed(2), BTC, 300, 7));
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 300);
+
+        // Only the owner of the NFT can pay for it
+        assert_noop!(Exchange::take_order(Origin::signed(3), 0, 1), "not the owner");
+        assert_ok!(Exchange::take_order(Origin::signed(1), 0, 1));
+
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(MockNft::owner(7), Some(2));
+        assert_eq!(MockNft::trades(7), vec![(1, 2, Some((BTC, 300)))]);
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 0);
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_700);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_000_300);
+    })
+}
+
+#[test]
+fn cancel_nft_offer_unlocks_the_nft() {
+    new_test_ext().execute_with(|| {
+        MockNft::mint(1, 7);
+        assert_ok!(Exchange::offer_nft(Origin::signed(1), 7, DOT, 500));
+
+        assert_noop!(Exchange::cancel_order(Origin::signed(2), 0), Error::<Test>::NotOwner);
+        assert_ok!(Exchange::cancel_order(Origin::signed(1), 0));
+
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderCancelled(0)));
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert!(!MockNft::is_locked(7));
+        assert_eq!(MockNft::owner(7), Some(1));
+        assert_noop!(Exchange::take_order(Origin::signed(2), 0, 500), Error::<Test>::InvalidOrderId);
+    })
+}
+
+// Order 0: 1 gives 1000 DOT for 3000 BTC
+fn dot_for_btc_order() {
+    assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 1000, BTC, 3000));
+    assert_eq!(Tokens::reserved_balance(DOT, &1), 1000);
+}
+
+#[test]
+fn take_order_fills_part_of_an_order() {
+    new_test_ext().execute_with(|| {
+        dot_for_btc_order();
+
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 300));
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderPartiallyFilled(2, 0, 300, 100)));
+        assert_eq!(Orders::<Test>::get(0).unwrap().filled, 300);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_000_300);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 900);
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_700);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_100);
+
+        // Taking more than is left only pays for the rest
+        assert_ok!(Exchange::take_order(Origin::signed(3), 0, 5000));
+        let order = Order {
+            base: OrderAsset::Currency(DOT, 1000),
+            target: OrderAsset::Currency(BTC, 3000),
+            filled: 3000,
+            owner: 1,
+        };
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderTaken(3, 0, order)));
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(Tokens::free_balance(BTC, &3), 997_300);
+        assert_eq!(Tokens::free_balance(DOT, &3), 1_000_900);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_003_000);
+        assert_eq!(Tokens::free_balance(DOT, &1), 999_000);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 0);
+    })
+}
+
+#[test]
+fn partial_fills_round_in_favour_of_the_owner() {
+    new_test_ext().execute_with(|| {
+        dot_for_btc_order();
+
+        // 4 BTC are worth 1.33 DOT: each fill pays out what the total filled so far is worth,
+        // rounded down, minus what was paid out before
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 4));
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderPartiallyFilled(2, 0, 4, 1)));
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 4));
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderPartiallyFilled(2, 0, 4, 1)));
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 4));
+        assert_eq!(last_event(), TestEvent::exchange(Event::<Test>::OrderPartiallyFilled(2, 0, 4, 2)));
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_004);
+
+        // The last fill pays out the whole rest of the base
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 2988));
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_001_000);
+        assert_eq!(Tokens::free_balance(BTC, &2), 997_000);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 0);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_003_000);
+    })
+}
+
+#[test]
+fn take_order_rejects_fills_paying_out_nothing() {
+    new_test_ext().execute_with(|| {
+        dot_for_btc_order();
+
+        assert_noop!(Exchange::take_order(Origin::signed(2), 0, 2), Error::<Test>::FillTooSmall);
+        assert_noop!(Exchange::take_order(Origin::signed(2), 0, 0), Error::<Test>::ZeroAmount);
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 3));
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_001);
+    })
+}
+
+#[test]
+fn nft_orders_are_filled_whole() {
+    new_test_ext().execute_with(|| {
+        MockNft::mint(1, 7);
+        assert_ok!(Exchange::offer_nft(Origin::signed(1), 7, DOT, 500));
+
+        assert_noop!(Exchange::take_order(Origin::signed(2), 0, 200), Error::<Test>::OrderNotDivisible);
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 500));
+        assert_eq!(MockNft::owner(7), Some(2));
+    })
+}
+
+#[test]
+fn cancel_order_refunds_the_unfilled_base() {
+    new_test_ext().execute_with(|| {
+        dot_for_btc_order();
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 300));
+        assert_ok!(Exchange::take_order(Origin::signed(2), 0, 4));
+
+        // 101 DOT were paid out for 304 BTC, the other 899 go back to the owner
+        assert_ok!(Exchange::cancel_order(Origin::signed(1), 0));
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 0);
+        assert_eq!(Tokens::free_balance(DOT, &1), 999_899);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_101);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_000_304);
+    })
+}
+
+fn book(base_currency_id: CurrencyId, target_currency_id: CurrencyId) -> Vec<u32> {
+    Exchange::order_book((base_currency_id, target_currency_id)).into_iter().map(|(_, _, order_id)| order_id).collect()
+}
+
+#[test]
+fn submit_order_rejects_identical_currencies() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Exchange::submit_order(Origin::signed(1), DOT, 100, DOT, 200), Error::<Test>::SameCurrency);
+    })
+}
+
+#[test]
+fn crossing_orders_match_at_the_resting_price() {
+    new_test_ext().execute_with(|| {
+        // 2 BTC per DOT
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 100, BTC, 200));
+        assert_eq!(book(DOT, BTC), vec![0]);
+
+        // Willing to pay up to 3 BTC per DOT, but only pays 2
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 300, DOT, 100));
+        assert!(System::events().iter().any(|record| {
+            record.event == TestEvent::exchange(Event::<Test>::OrdersMatched(1, 0, 200, 100))
+        }));
+
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(Orders::<Test>::get(1), None);
+        assert_eq!(book(DOT, BTC), Vec::<u32>::new());
+        assert_eq!(book(BTC, DOT), Vec::<u32>::new());
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_800);
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 0);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_100);
+        assert_eq!(Tokens::free_balance(DOT, &1), 999_900);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 0);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_000_200);
+    })
+}
+
+#[test]
+fn orders_that_dont_cross_rest_on_the_book() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 100, BTC, 200));
+        // Only willing to pay 1.5 BTC per DOT
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 150, DOT, 100));
+
+        assert_eq!(Exchange::order_book((DOT, BTC)), vec![(100, 200, 0)]);
+        assert_eq!(Exchange::order_book((BTC, DOT)), vec![(150, 100, 1)]);
+        assert_eq!(Tokens::reserved_balance(DOT, &1), 100);
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 150);
+    })
+}
+
+#[test]
+fn book_is_sorted_by_exact_price() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Tokens::deposit(DOT, &3, 1_000_000_000_000_000_000));
+
+        // A third of a BTC per DOT
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 3, BTC, 1));
+        // Slightly less, which rounds to the same 18 decimals
+        assert_ok!(Exchange::submit_order(Origin::signed(3), DOT, 1_000_000_000_000_000_000, BTC, 333_333_333_333_333_333));
+        // Exactly the first price
+        assert_ok!(Exchange::submit_order(Origin::signed(4), DOT, 6, BTC, 2));
+
+        assert_eq!(book(DOT, BTC), vec![1, 0, 2]);
+    })
+}
+
+#[test]
+fn equal_prices_match_oldest_first() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 100, BTC, 200));
+        assert_ok!(Exchange::submit_order(Origin::signed(3), DOT, 100, BTC, 200));
+        assert_ok!(Exchange::submit_order(Origin::signed(4), DOT, 50, BTC, 100));
+
+        // Takes all of order 0 and half of order 1
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 300, DOT, 150));
+
+        // Closed orders leave the front of the book, the partially filled one stays first
+        assert_eq!(Exchange::order_book((DOT, BTC)), vec![(100, 200, 1), (50, 100, 2)]);
+        assert_eq!(Orders::<Test>::get(0), None);
+        assert_eq!(Orders::<Test>::get(1).unwrap().filled, 100);
+        assert_eq!(Orders::<Test>::get(3), None);
+        assert_eq!(Tokens::free_balance(BTC, &1), 1_000_200);
+        assert_eq!(Tokens::free_balance(BTC, &3), 1_000_100);
+        assert_eq!(Tokens::free_balance(BTC, &4), 1_000_000);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_150);
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_700);
+    })
+}
+
+#[test]
+fn matching_stops_after_max_matches_per_order() {
+    new_test_ext().execute_with(|| {
+        for &maker in [1, 3, 4, 1].iter() {
+            assert_ok!(Exchange::submit_order(Origin::signed(maker), DOT, 10, BTC, 20));
+        }
+
+        let info = Exchange::submit_order(Origin::signed(2), BTC, 100, DOT, 50).unwrap();
+        assert_eq!(info.actual_weight, Some(<() as WeightInfo>::submit_order(3, 4)));
+
+        // Three orders are taken and the rest of the new order rests at its own price
+        assert_eq!(book(DOT, BTC), vec![3]);
+        assert_eq!(Exchange::order_book((BTC, DOT)), vec![(40, 20, 4)]);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_030);
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_900);
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 40);
+    })
+}
+
+#[test]
+fn matching_refunds_what_the_rest_does_not_need() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 10, BTC, 20));
+
+        // Gets 10 DOT for 20 BTC, the other 20 DOT are asked for at the original price of
+        // 100 BTC for 30 DOT, which is 66 BTC rounded down
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 100, DOT, 30));
+
+        let order = Order {
+            base: OrderAsset::Currency(BTC, 66),
+            target: OrderAsset::Currency(DOT, 20),
+            filled: 0,
+            owner: 2,
+        };
+        assert_eq!(Orders::<Test>::get(1), Some(order));
+        assert_eq!(Tokens::reserved_balance(BTC, &2), 66);
+        assert_eq!(Tokens::free_balance(BTC, &2), 999_914);
+        assert_eq!(Tokens::free_balance(DOT, &2), 1_000_010);
+    })
+}
+
+#[test]
+fn full_books_reject_resting_orders() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..8 {
+            assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 10, BTC, 20));
+        }
+        assert_noop!(Exchange::submit_order(Origin::signed(3), DOT, 10, BTC, 20), Error::<Test>::OrderBookFull);
+        // A crossing order that is filled whole doesn't need room on its book
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 20, DOT, 10));
+        assert_eq!(book(DOT, BTC).len(), 7);
+        assert_ok!(Exchange::submit_order(Origin::signed(3), DOT, 10, BTC, 20));
+    })
+}
+
+#[test]
+fn stale_book_entries_are_dropped_when_matching() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 10, BTC, 20));
+        assert_ok!(Exchange::submit_order(Origin::signed(3), DOT, 10, BTC, 20));
+        Orders::<Test>::remove(0);
+
+        assert_ok!(Exchange::submit_order(Origin::signed(2), BTC, 20, DOT, 10));
+        assert_eq!(book(DOT, BTC), Vec::<u32>::new());
+        assert_eq!(Tokens::free_balance(BTC, &3), 1_000_020);
+    })
+}
+
+#[test]
+fn take_and_cancel_charge_for_the_book() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..3 {
+            assert_ok!(Exchange::submit_order(Origin::signed(1), DOT, 10, BTC, 20));
+        }
+
+        let info = Exchange::take_order(Origin::signed(2), 0, 20).unwrap();
+        assert_eq!(info.actual_weight, Some(<() as WeightInfo>::take_order(3)));
+        // A partial fill leaves the book alone
+        let info = Exchange::take_order(Origin::signed(2), 1, 10).unwrap();
+        assert_eq!(info.actual_weight, Some(<() as WeightInfo>::take_order(0)));
+        let info = Exchange::cancel_order(Origin::signed(1), 2).unwrap();
+        assert_eq!(info.actual_weight, Some(<() as WeightInfo>::cancel_order(2)));
+        assert_eq!(book(DOT, BTC), vec![1]);
+    })
+}
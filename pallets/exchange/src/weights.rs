@@ -0,0 +1,101 @@
+//! Weights for pallet_exchange.
+//!
+//! Estimated until `make benchmark` is run on reference hardware, which overwrites this file.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{Weight, constants::RocksDbWeight};
+use frame_support::traits::Get;
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_exchange.
+pub trait WeightInfo {
+    fn submit_order(m: u32, b: u32, ) -> Weight;
+    fn offer_nft() -> Weight;
+    fn request_nft() -> Weight;
+    fn take_order(b: u32, ) -> Weight;
+    fn take_nft_order() -> Weight;
+    fn cancel_order(b: u32, ) -> Weight;
+}
+
+/// Weights for pallet_exchange using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn submit_order(m: u32, b: u32, ) -> Weight {
+        (72_400_000 as Weight)
+            .saturating_add((88_600_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add((412_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(m as Weight)))
+    }
+    fn offer_nft() -> Weight {
+        (52_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn request_nft() -> Weight {
+        (64_900_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn take_order(b: u32, ) -> Weight {
+        (118_300_000 as Weight)
+            .saturating_add((236_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn take_nft_order() -> Weight {
+        (241_700_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(13 as Weight))
+    }
+    fn cancel_order(b: u32, ) -> Weight {
+        (45_200_000 as Weight)
+            .saturating_add((236_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn submit_order(m: u32, b: u32, ) -> Weight {
+        (72_400_000 as Weight)
+            .saturating_add((88_600_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add((412_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(m as Weight)))
+    }
+    fn offer_nft() -> Weight {
+        (52_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn request_nft() -> Weight {
+        (64_900_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn take_order(b: u32, ) -> Weight {
+        (118_300_000 as Weight)
+            .saturating_add((236_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn take_nft_order() -> Weight {
+        (241_700_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(13 as Weight))
+    }
+    fn cancel_order(b: u32, ) -> Weight {
+        (45_200_000 as Weight)
+            .saturating_add((236_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+}
@@ -0,0 +1,40 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::Perbill;
+use sp_std::prelude::*;
+
+pub use pallet_kitties::{CommonAncestor, KittyInfo, KittyMetadata, PedigreeNode, Phenotype, ProvenanceEntry, Rental, TransferKind};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber> where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// A single kitty with its DNA, owner, current user, price, parents and metadata.
+		fn kitty(kitty_id: KittyIndex) -> Option<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>;
+		/// Kitties owned by `owner`, skipping `start` and returning at most `limit`.
+		fn kitties_of_owner(owner: AccountId, start: u32, limit: u32) -> Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>;
+		/// Kitties that currently have an asking price.
+		fn kitties_for_sale(start: u32, limit: u32) -> Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>;
+		/// Current price of a kitty in a Dutch auction.
+		fn dutch_auction_price(kitty_id: KittyIndex) -> Option<Balance>;
+		/// Visible traits decoded from a kitty's DNA.
+		fn phenotype(kitty_id: KittyIndex) -> Option<Phenotype>;
+		/// Ancestors of a kitty up to `depth` generations, breadth first and each listed once.
+		fn pedigree(kitty_id: KittyIndex, depth: u32) -> Vec<PedigreeNode<KittyIndex>>;
+		/// Nearest ancestor shared by two kitties.
+		fn common_ancestor(kitty_id_1: KittyIndex, kitty_id_2: KittyIndex) -> Option<CommonAncestor<KittyIndex>>;
+		/// Wright's inbreeding coefficient of a kitty.
+		fn inbreeding_coefficient(kitty_id: KittyIndex) -> Option<Perbill>;
+		/// Inbreeding coefficient a child of the two kitties would have.
+		fn pairing_inbreeding_coefficient(kitty_id_1: KittyIndex, kitty_id_2: KittyIndex) -> Option<Perbill>;
+		/// Latest ownership changes of a kitty with what was paid, oldest first.
+		fn provenance(kitty_id: KittyIndex) -> Vec<ProvenanceEntry<AccountId, CurrencyId, Balance, BlockNumber>>;
+	}
+}
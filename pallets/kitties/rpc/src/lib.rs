@@ -0,0 +1,256 @@
+//! RPC interface for the kitties pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, Perbill};
+
+use pallet_kitties_rpc_runtime_api::{CommonAncestor, KittyInfo, PedigreeNode, Phenotype, ProvenanceEntry};
+pub use pallet_kitties_rpc_runtime_api::KittiesApi as KittiesRuntimeApi;
+
+#[rpc]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, CurrencyId, Balance, BlockNumber> {
+	#[rpc(name = "kitties_getKitty")]
+	fn kitty(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>>;
+
+	#[rpc(name = "kitties_kittiesOfOwner")]
+	fn kitties_of_owner(
+		&self,
+		owner: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>>;
+
+	#[rpc(name = "kitties_kittiesForSale")]
+	fn kitties_for_sale(
+		&self,
+		start: u32,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>>;
+
+	#[rpc(name = "kitties_dutchAuctionPrice")]
+	fn dutch_auction_price(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Balance>>;
+
+	#[rpc(name = "kitties_phenotype")]
+	fn phenotype(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Phenotype>>;
+
+	#[rpc(name = "kitties_pedigree")]
+	fn pedigree(
+		&self,
+		kitty_id: KittyIndex,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PedigreeNode<KittyIndex>>>;
+
+	#[rpc(name = "kitties_commonAncestor")]
+	fn common_ancestor(
+		&self,
+		kitty_id_1: KittyIndex,
+		kitty_id_2: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<CommonAncestor<KittyIndex>>>;
+
+	#[rpc(name = "kitties_inbreedingCoefficient")]
+	fn inbreeding_coefficient(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Perbill>>;
+
+	#[rpc(name = "kitties_pairingInbreedingCoefficient")]
+	fn pairing_inbreeding_coefficient(
+		&self,
+		kitty_id_1: KittyIndex,
+		kitty_id_2: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Perbill>>;
+
+	#[rpc(name = "kitties_provenance")]
+	fn provenance(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ProvenanceEntry<AccountId, CurrencyId, Balance, BlockNumber>>>;
+}
+
+/// A struct that implements the [`KittiesApi`].
+pub struct Kitties<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Kitties<C, B> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Kitties { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query kitties.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>
+	KittiesApi<<Block as BlockT>::Hash, AccountId, KittyIndex, CurrencyId, Balance, BlockNumber> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>,
+	AccountId: Codec,
+	KittyIndex: Codec,
+	CurrencyId: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+{
+	fn kitty(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn kitties_of_owner(
+		&self,
+		owner: AccountId,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitties_of_owner(&at, owner, start, limit).map_err(runtime_error)
+	}
+
+	fn kitties_for_sale(
+		&self,
+		start: u32,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitties_for_sale(&at, start, limit).map_err(runtime_error)
+	}
+
+	fn dutch_auction_price(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.dutch_auction_price(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn phenotype(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Phenotype>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.phenotype(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn pedigree(
+		&self,
+		kitty_id: KittyIndex,
+		depth: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PedigreeNode<KittyIndex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pedigree(&at, kitty_id, depth).map_err(runtime_error)
+	}
+
+	fn common_ancestor(
+		&self,
+		kitty_id_1: KittyIndex,
+		kitty_id_2: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<CommonAncestor<KittyIndex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.common_ancestor(&at, kitty_id_1, kitty_id_2).map_err(runtime_error)
+	}
+
+	fn inbreeding_coefficient(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Perbill>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.inbreeding_coefficient(&at, kitty_id).map_err(runtime_error)
+	}
+
+	fn pairing_inbreeding_coefficient(
+		&self,
+		kitty_id_1: KittyIndex,
+		kitty_id_2: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Perbill>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pairing_inbreeding_coefficient(&at, kitty_id_1, kitty_id_2).map_err(runtime_error)
+	}
+
+	fn provenance(
+		&self,
+		kitty_id: KittyIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProvenanceEntry<AccountId, CurrencyId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.provenance(&at, kitty_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,451 @@
+//! Benchmarks for the kitties pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_support::traits::{OnInitialize, OnFinalize};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn funded<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 1_000u32.into());
+	who
+}
+
+fn funded_caller<T: Trait>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 1_000u32.into());
+	caller
+}
+
+/// Create a staked generation 0 kitty of the given sex, bypassing the random DNA.
+fn new_kitty<T: Trait>(owner: &T::AccountId, sex: Sex) -> T::KittyIndex {
+	let kitty_id = Module::<T>::next_kitty_id().unwrap();
+	let mut dna = [0u8; 16];
+	if sex == Sex::Female {
+		dna[genes::SEX] = 1;
+	}
+	let stake = T::StakeForKitty::get();
+	T::Currency::reserve(owner, stake).unwrap();
+	Module::<T>::insert_kitty(owner, kitty_id, Module::<T>::new_kitty(dna, 0), stake);
+	kitty_id
+}
+
+/// Give a kitty the longest family lists it can have while still breeding with a new partner,
+/// the worst case for breeding.
+fn full_family<T: Trait>(kitty_id: T::KittyIndex) {
+	let relatives = (1..FAMILY_WEIGHT_BOUND).map(|i| T::KittyIndex::from(1_000_000 + i)).collect::<Vec<_>>();
+	FamilyMap::<T>::insert(kitty_id, Relation::Children, &relatives);
+	FamilyMap::<T>::insert(kitty_id, Relation::Partner, &relatives);
+}
+
+/// Give a kitty the longest provenance history kept, the worst case for moving it as the history
+/// is rewritten.
+fn full_provenance<T: Trait>(kitty_id: T::KittyIndex, owner: &T::AccountId) {
+	let entry = ProvenanceEntry {
+		from: owner.clone(),
+		to: owner.clone(),
+		block: Zero::zero(),
+		kind: TransferKind::Sale,
+		price: Some((T::GetNativeCurrencyId::get(), price::<T>())),
+	};
+	Provenance::<T>::insert(kitty_id, vec![entry; T::MaxProvenanceEntries::get() as usize]);
+}
+
+fn price<T: Trait>() -> BalanceOf<T> {
+	T::StakeForKitty::get()
+}
+
+fn auction_end<T: Trait>() -> T::BlockNumber {
+	<frame_system::Module<T>>::block_number() + 10u32.into()
+}
+
+benchmarks! {
+	_ { }
+
+	create {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(T::KittyIndex::zero()), Some(caller));
+	}
+
+	transfer {
+		let caller = funded_caller::<T>();
+		let to = funded::<T>("to", 0);
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		full_provenance::<T>(kitty_id, &caller);
+	}: _(RawOrigin::Signed(caller), to.clone(), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	breed {
+		let caller = funded_caller::<T>();
+		let sire = new_kitty::<T>(&caller, Sex::Male);
+		let matron = new_kitty::<T>(&caller, Sex::Female);
+		full_family::<T>(sire);
+		full_family::<T>(matron);
+	}: _(RawOrigin::Signed(caller.clone()), sire, matron)
+	verify {
+		assert_eq!(Module::<T>::parents_of(matron + One::one()), Some((sire, matron)));
+	}
+
+	commit_dna {
+		let caller = funded_caller::<T>();
+		let commitment = T::Hashing::hash_of(&[0u8; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), commitment)
+	verify {
+		assert!(Module::<T>::dna_commitment(caller, commitment).is_some());
+	}
+
+	reveal_create {
+		let caller = funded_caller::<T>();
+		let secret = [0u8; 32];
+		Module::<T>::commit_dna(RawOrigin::Signed(caller.clone()).into(), T::Hashing::hash_of(&secret))?;
+		<frame_system::Module<T>>::set_block_number(<frame_system::Module<T>>::block_number() + T::RevealDelay::get() + One::one());
+	}: _(RawOrigin::Signed(caller.clone()), secret)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(T::KittyIndex::zero()), Some(caller));
+	}
+
+	reveal_breed {
+		let caller = funded_caller::<T>();
+		let sire = new_kitty::<T>(&caller, Sex::Male);
+		let matron = new_kitty::<T>(&caller, Sex::Female);
+		full_family::<T>(sire);
+		full_family::<T>(matron);
+		let secret = [0u8; 32];
+		Module::<T>::commit_dna(RawOrigin::Signed(caller.clone()).into(), T::Hashing::hash_of(&secret))?;
+		<frame_system::Module<T>>::set_block_number(<frame_system::Module<T>>::block_number() + T::RevealDelay::get() + One::one());
+	}: _(RawOrigin::Signed(caller.clone()), sire, matron, secret)
+	verify {
+		assert_eq!(Module::<T>::parents_of(matron + One::one()), Some((sire, matron)));
+	}
+
+	clear_dna_commitment {
+		let committer = funded::<T>("committer", 0);
+		let caller = funded_caller::<T>();
+		let commitment = T::Hashing::hash_of(&[0u8; 32]);
+		Module::<T>::commit_dna(RawOrigin::Signed(committer.clone()).into(), commitment)?;
+		let expired = T::RevealDelay::get() + T::RevealWindow::get() + One::one();
+		<frame_system::Module<T>>::set_block_number(<frame_system::Module<T>>::block_number() + expired);
+	}: _(RawOrigin::Signed(caller), committer.clone(), commitment)
+	verify {
+		assert!(Module::<T>::dna_commitment(committer, commitment).is_none());
+	}
+
+	transfer_batch {
+		let n in 1 .. MAX_BATCH_SIZE;
+		let caller = funded_caller::<T>();
+		let to = funded::<T>("to", 0);
+		let kitty_ids = (0..n).map(|_| new_kitty::<T>(&caller, Sex::Male)).collect::<Vec<_>>();
+		for &kitty_id in kitty_ids.iter() {
+			full_provenance::<T>(kitty_id, &caller);
+		}
+	}: _(RawOrigin::Signed(caller), to.clone(), kitty_ids)
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(to), n);
+	}
+
+	breed_batch {
+		let n in 1 .. MAX_BATCH_SIZE;
+		let caller = funded_caller::<T>();
+		let pairs = (0..n)
+			.map(|_| (new_kitty::<T>(&caller, Sex::Male), new_kitty::<T>(&caller, Sex::Female)))
+			.collect::<Vec<_>>();
+		for &(sire, matron) in pairs.iter() {
+			full_family::<T>(sire);
+			full_family::<T>(matron);
+		}
+	}: _(RawOrigin::Signed(caller.clone()), pairs)
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(caller), 3 * n);
+	}
+
+	release {
+		let p in 0 .. FAMILY_WEIGHT_BOUND;
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		let partners = (0..p).map(|i| T::KittyIndex::from(1_000 + i)).collect::<Vec<_>>();
+		// Every partner has a full list of partners to search
+		for partner in partners.iter() {
+			let mut their_partners = (1..FAMILY_WEIGHT_BOUND).map(|i| T::KittyIndex::from(1_000_000 + i)).collect::<Vec<_>>();
+			their_partners.push(kitty_id);
+			FamilyMap::<T>::insert(partner, Relation::Partner, their_partners);
+		}
+		FamilyMap::<T>::insert(kitty_id, Relation::Partner, partners);
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitties(kitty_id), None);
+	}
+
+	approve {
+		let caller = funded_caller::<T>();
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+	}: _(RawOrigin::Signed(caller), Some(spender.clone()), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::approved(kitty_id), Some(spender));
+	}
+
+	set_approval_for_all {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(Module::<T>::is_approved_for_all(caller, operator));
+	}
+
+	transfer_from {
+		let owner = funded::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		let to = funded::<T>("to", 0);
+		let kitty_id = new_kitty::<T>(&owner, Sex::Male);
+		full_provenance::<T>(kitty_id, &owner);
+		Module::<T>::approve(RawOrigin::Signed(owner.clone()).into(), Some(caller.clone()), kitty_id)?;
+	}: _(RawOrigin::Signed(caller), owner, to.clone(), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	offer_sire {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(price::<T>()))
+	verify {
+		assert_eq!(Module::<T>::sire_fee(kitty_id), Some(price::<T>()));
+	}
+
+	breed_with_sire {
+		let caller = funded_caller::<T>();
+		let sire_owner = funded::<T>("sire_owner", 0);
+		let matron = new_kitty::<T>(&caller, Sex::Female);
+		let sire = new_kitty::<T>(&sire_owner, Sex::Male);
+		full_family::<T>(matron);
+		full_family::<T>(sire);
+		Module::<T>::offer_sire(RawOrigin::Signed(sire_owner).into(), sire, Some(price::<T>()))?;
+	}: _(RawOrigin::Signed(caller.clone()), matron, sire, price::<T>())
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(sire + One::one()), Some(caller));
+	}
+
+	set_metadata {
+		let b in 0 .. T::MaxNameLength::get() + T::MaxDescriptionLength::get();
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		// Replacing existing metadata is the worst case
+		Module::<T>::set_metadata(RawOrigin::Signed(caller.clone()).into(), kitty_id, vec![0u8], vec![])?;
+		let name_len = b.min(T::MaxNameLength::get());
+		let name = vec![0u8; name_len as usize];
+		let description = vec![0u8; (b - name_len) as usize];
+	}: _(RawOrigin::Signed(caller), kitty_id, name, description)
+	verify {
+		assert!(Module::<T>::metadata(kitty_id).is_some());
+	}
+
+	clear_metadata {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		Module::<T>::set_metadata(RawOrigin::Signed(caller.clone()).into(), kitty_id, vec![0u8], vec![])?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::metadata(kitty_id), None);
+	}
+
+	ask {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		let listing = (T::GetNativeCurrencyId::get(), price::<T>());
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(listing))
+	verify {
+		assert_eq!(Module::<T>::kitty_price(kitty_id), Some(listing));
+	}
+
+	buy {
+		let seller = funded::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&seller, Sex::Male);
+		full_provenance::<T>(kitty_id, &seller);
+		let currency_id = T::GetNativeCurrencyId::get();
+		T::MultiCurrency::deposit(currency_id, &caller, price::<T>())?;
+		Module::<T>::ask(RawOrigin::Signed(seller).into(), kitty_id, Some((currency_id, price::<T>())))?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, currency_id, price::<T>())
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	start_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		let end = auction_end::<T>();
+	}: _(RawOrigin::Signed(caller), kitty_id, price::<T>(), end)
+	verify {
+		assert!(Module::<T>::auction(kitty_id).is_some());
+	}
+
+	bid {
+		let seller = funded::<T>("seller", 0);
+		let outbid = funded::<T>("bidder", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&seller, Sex::Male);
+		Module::<T>::start_auction(RawOrigin::Signed(seller).into(), kitty_id, price::<T>(), auction_end::<T>())?;
+		// Outbidding someone, who has to be refunded, is the worst case
+		Module::<T>::bid(RawOrigin::Signed(outbid).into(), kitty_id, price::<T>())?;
+		let amount = price::<T>() + price::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, amount)
+	verify {
+		assert_eq!(Module::<T>::auction(kitty_id).unwrap().best_bid, Some((caller, amount)));
+	}
+
+	cancel_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		Module::<T>::start_auction(RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), auction_end::<T>())?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::auction(kitty_id).is_none());
+	}
+
+	start_dutch_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		let start_price = price::<T>() + price::<T>();
+	}: _(RawOrigin::Signed(caller), kitty_id, start_price, price::<T>(), 10u32.into())
+	verify {
+		assert!(Module::<T>::dutch_auction(kitty_id).is_some());
+	}
+
+	buy_dutch {
+		let seller = funded::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&seller, Sex::Male);
+		full_provenance::<T>(kitty_id, &seller);
+		let start_price = price::<T>() + price::<T>();
+		Module::<T>::start_dutch_auction(
+			RawOrigin::Signed(seller).into(), kitty_id, start_price, price::<T>(), 10u32.into()
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, start_price)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	cancel_dutch_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		Module::<T>::start_dutch_auction(
+			RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), price::<T>(), 10u32.into()
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::dutch_auction(kitty_id).is_none());
+	}
+
+	offer_rental {
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&caller, Sex::Male);
+		let offer = RentalOffer { price: price::<T>(), duration: 10u32.into() };
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(offer.clone()))
+	verify {
+		assert_eq!(Module::<T>::rental_offer(kitty_id), Some(offer));
+	}
+
+	rent {
+		let owner = funded::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = new_kitty::<T>(&owner, Sex::Male);
+		// Cancelling a listing is the worst case
+		Module::<T>::ask(RawOrigin::Signed(owner.clone()).into(), kitty_id, Some((T::GetNativeCurrencyId::get(), price::<T>())))?;
+		let offer = RentalOffer { price: price::<T>(), duration: 10u32.into() };
+		Module::<T>::offer_rental(RawOrigin::Signed(owner).into(), kitty_id, Some(offer))?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, price::<T>())
+	verify {
+		assert_eq!(Module::<T>::kitty_user(kitty_id), Some(caller));
+	}
+
+	on_initialize {
+		let r in 0 .. T::MaxRentalsPerBlock::get();
+		let renter = funded::<T>("renter", 0);
+		let duration: T::BlockNumber = 10u32.into();
+		let end = <frame_system::Module<T>>::block_number() + duration;
+		let mut kitty_ids = Vec::new();
+		for i in 0..r {
+			let owner = funded::<T>("owner", i);
+			let kitty_id = new_kitty::<T>(&owner, Sex::Male);
+			let offer = RentalOffer { price: price::<T>(), duration };
+			Module::<T>::offer_rental(RawOrigin::Signed(owner).into(), kitty_id, Some(offer))?;
+			Module::<T>::rent(RawOrigin::Signed(renter.clone()).into(), kitty_id, price::<T>())?;
+			kitty_ids.push(kitty_id);
+		}
+	}: {
+		Module::<T>::on_initialize(end);
+	}
+	verify {
+		for kitty_id in kitty_ids {
+			assert!(Module::<T>::rental(kitty_id).is_none());
+		}
+	}
+
+	on_finalize {
+		let a in 0 .. T::MaxAuctionsPerBlock::get();
+		let end = auction_end::<T>();
+		let bidder = funded::<T>("bidder", 0);
+		for i in 0..a {
+			let seller = funded::<T>("seller", i);
+			let kitty_id = new_kitty::<T>(&seller, Sex::Male);
+			full_provenance::<T>(kitty_id, &seller);
+			Module::<T>::start_auction(RawOrigin::Signed(seller).into(), kitty_id, price::<T>(), end)?;
+			Module::<T>::bid(RawOrigin::Signed(bidder.clone()).into(), kitty_id, price::<T>())?;
+		}
+	}: {
+		Module::<T>::on_finalize(end);
+	}
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(bidder), a);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_breed::<Test>());
+			assert_ok!(test_benchmark_commit_dna::<Test>());
+			assert_ok!(test_benchmark_reveal_create::<Test>());
+			assert_ok!(test_benchmark_reveal_breed::<Test>());
+			assert_ok!(test_benchmark_clear_dna_commitment::<Test>());
+			assert_ok!(test_benchmark_transfer_batch::<Test>());
+			assert_ok!(test_benchmark_breed_batch::<Test>());
+			assert_ok!(test_benchmark_release::<Test>());
+			assert_ok!(test_benchmark_approve::<Test>());
+			assert_ok!(test_benchmark_set_approval_for_all::<Test>());
+			assert_ok!(test_benchmark_transfer_from::<Test>());
+			assert_ok!(test_benchmark_offer_sire::<Test>());
+			assert_ok!(test_benchmark_breed_with_sire::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_metadata::<Test>());
+			assert_ok!(test_benchmark_ask::<Test>());
+			assert_ok!(test_benchmark_buy::<Test>());
+			assert_ok!(test_benchmark_start_auction::<Test>());
+			assert_ok!(test_benchmark_bid::<Test>());
+			assert_ok!(test_benchmark_cancel_auction::<Test>());
+			assert_ok!(test_benchmark_start_dutch_auction::<Test>());
+			assert_ok!(test_benchmark_buy_dutch::<Test>());
+			assert_ok!(test_benchmark_cancel_dutch_auction::<Test>());
+			assert_ok!(test_benchmark_offer_rental::<Test>());
+			assert_ok!(test_benchmark_rent::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_on_finalize::<Test>());
+		});
+	}
+}
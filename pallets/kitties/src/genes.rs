@@ -0,0 +1,137 @@
+//! Layout of the 16 DNA bytes and their decoding into visible traits.
+//!
+//! | byte  | gene                                              |
+//! |-------|---------------------------------------------------|
+//! | 0     | sex, lowest bit                                   |
+//! | 1     | body colour                                       |
+//! | 2     | eye shape                                         |
+//! | 3     | eye colour                                        |
+//! | 4     | pattern                                           |
+//! | 5     | pattern colour                                    |
+//! | 6     | rarity tier                                       |
+//! | 7..16 | hidden, inherited but without a visible trait yet |
+//!
+//! Decoding is pure, so it can be used by the runtime and by clients alike.
+
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Permill};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+use crate::Sex;
+
+pub const SEX: usize = 0;
+pub const BODY_COLOUR: usize = 1;
+pub const EYE_SHAPE: usize = 2;
+pub const EYE_COLOUR: usize = 3;
+pub const PATTERN: usize = 4;
+pub const PATTERN_COLOUR: usize = 5;
+pub const RARITY: usize = 6;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Colour {
+	Black,
+	White,
+	Grey,
+	Ginger,
+	Cream,
+	Brown,
+	Blue,
+	Lilac,
+}
+
+const COLOURS: [Colour; 8] = [
+	Colour::Black, Colour::White, Colour::Grey, Colour::Ginger,
+	Colour::Cream, Colour::Brown, Colour::Blue, Colour::Lilac,
+];
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EyeShape {
+	Round,
+	Almond,
+	Sleepy,
+	Wide,
+}
+
+const EYE_SHAPES: [EyeShape; 4] = [EyeShape::Round, EyeShape::Almond, EyeShape::Sleepy, EyeShape::Wide];
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Pattern {
+	Solid,
+	Tabby,
+	Spotted,
+	Calico,
+}
+
+const PATTERNS: [Pattern; 4] = [Pattern::Solid, Pattern::Tabby, Pattern::Spotted, Pattern::Calico];
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Rarity {
+	/// Gene values 0..192
+	Common,
+	/// Gene values 192..240
+	Uncommon,
+	/// Gene values 240..254
+	Rare,
+	/// Gene values 254 and 255
+	Legendary,
+}
+
+/// Visible traits of a kitty, decoded from its DNA.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Phenotype {
+	pub sex: Sex,
+	pub body_colour: Colour,
+	pub eye_shape: EyeShape,
+	pub eye_colour: Colour,
+	pub pattern: Pattern,
+	pub pattern_colour: Colour,
+	pub rarity: Rarity,
+}
+
+pub fn sex(dna: &[u8; 16]) -> Sex {
+	match dna[SEX] & 1 {
+		0 => Sex::Male,
+		_ => Sex::Female,
+	}
+}
+
+pub fn rarity(dna: &[u8; 16]) -> Rarity {
+	match dna[RARITY] {
+		0..=191 => Rarity::Common,
+		192..=239 => Rarity::Uncommon,
+		240..=253 => Rarity::Rare,
+		_ => Rarity::Legendary,
+	}
+}
+
+pub fn decode_dna(dna: &[u8; 16]) -> Phenotype {
+	Phenotype {
+		sex: sex(dna),
+		body_colour: COLOURS[dna[BODY_COLOUR] as usize % COLOURS.len()],
+		eye_shape: EYE_SHAPES[dna[EYE_SHAPE] as usize % EYE_SHAPES.len()],
+		eye_colour: COLOURS[dna[EYE_COLOUR] as usize % COLOURS.len()],
+		pattern: PATTERNS[dna[PATTERN] as usize % PATTERNS.len()],
+		pattern_colour: COLOURS[dna[PATTERN_COLOUR] as usize % COLOURS.len()],
+		rarity: rarity(dna),
+	}
+}
+
+/// With probability `chance`, overwrite one gene of `dna` with a random value. Returns whether
+/// a mutation happened.
+///
+/// `entropy` bytes 0..4 decide whether to mutate, byte 4 picks the gene and byte 5 its new value.
+pub fn mutate(dna: &mut [u8; 16], entropy: &[u8; 16], chance: Permill) -> bool {
+	let roll = u32::from_le_bytes([entropy[0], entropy[1], entropy[2], entropy[3]]) % 1_000_000;
+	if roll >= chance.deconstruct() {
+		return false;
+	}
+	let gene = entropy[4] as usize % dna.len();
+	dna[gene] = entropy[5];
+	true
+}
@@ -2,16 +2,20 @@
 
 use codec::{Encode, Decode};
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error, sp_runtime, ensure,
-	StorageValue, StorageMap, Parameter, weights::Weight,
-	traits::{Randomness, Currency, ReservableCurrency, Get, ExistenceRequirement},
+	decl_module, decl_storage, decl_event, decl_error, sp_runtime, ensure, transactional,
+	StorageValue, StorageMap, StorageDoubleMap, IterableStorageMap, IterableStorageDoubleMap, Parameter, weights::Weight, dispatch::{DispatchResult, DispatchResultWithPostInfo},
+	traits::{Randomness, Currency, ReservableCurrency, Get, ExistenceRequirement, BalanceStatus},
 
 };
 use sp_io::hashing::blake2_128;
 use frame_system::ensure_signed;
-use sp_runtime::DispatchError;
-use sp_std::prelude::*;
-use sp_runtime::traits::{AtLeast32Bit, Bounded, One, Member, AtLeast32BitUnsigned};
+use sp_runtime::{DispatchError, RuntimeDebug, Perbill, Permill};
+use sp_std::{prelude::*, collections::btree_set::BTreeSet};
+use sp_runtime::traits::{AtLeast32Bit, Bounded, One, Zero, Member, AtLeast32BitUnsigned, Saturating, Hash};
+use orml_traits::MultiCurrency;
+use nft_traits::NonFungibleAsset;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 
 #[cfg(test)]
@@ -20,7 +24,50 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod migration;
+
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod genes;
+pub use genes::{Phenotype, decode_dna};
+
+pub mod pedigree;
+pub use pedigree::{PedigreeNode, CommonAncestor, MAX_PEDIGREE_DEPTH};
+
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type CurrencyIdOf<T> = <<T as Trait>::MultiCurrency as MultiCurrency<<T as frame_system::Trait>::AccountId>>::CurrencyId;
+type KittyOf<T> = Kitty<<T as frame_system::Trait>::BlockNumber>;
+type AuctionOf<T> = Auction<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type DutchAuctionOf<T> = DutchAuction<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type RentalOfferOf<T> = RentalOffer<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type RentalOf<T> = Rental<<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber>;
+type ProvenanceEntryOf<T> = ProvenanceEntry<
+	<T as frame_system::Trait>::AccountId,
+	CurrencyIdOf<T>,
+	BalanceOf<T>,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+type MetadataRecordOf<T> = MetadataRecord<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+type DnaCommitmentOf<T> = DnaCommitment<<T as frame_system::Trait>::BlockNumber, BalanceOf<T>>;
+type KittyInfoOf<T> = KittyInfo<
+	<T as frame_system::Trait>::AccountId,
+	<T as Trait>::KittyIndex,
+	CurrencyIdOf<T>,
+	BalanceOf<T>,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+/// Maximum number of kitties returned by a single paginated query.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of kitties or pairs in a single `transfer_batch` or `breed_batch`.
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+/// Longest list of children or partners a kitty can have. Calls walking family lists are charged
+/// for lists this long up front and the unused part is refunded.
+pub const FAMILY_WEIGHT_BOUND: u32 = 100;
 
 pub trait Trait: frame_system::Trait {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
@@ -32,35 +79,281 @@ pub trait Trait: frame_system::Trait {
 	/// The currency mechanism.
 	type Currency: ReservableCurrency<Self::AccountId>;
 	type StakeForKitty: Get<BalanceOf<Self>>;
+	/// Most auctions that can end in the same block, they are all settled in its `on_finalize`.
+	type MaxAuctionsPerBlock: Get<u32>;
+	/// Most rentals that can end in the same block, they are all ended in its `on_initialize`.
+	type MaxRentalsPerBlock: Get<u32>;
+
+	/// Currencies kitties can be listed in, sharing the balance type of `Currency`.
+	type MultiCurrency: MultiCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+	/// Currency of `Currency` in `MultiCurrency`, used for Dutch auctions and migrated listings.
+	type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
+
+	/// Blocks a generation 0 kitty has to rest after breeding, multiplied by generation + 1.
+	type BreedCooldown: Get<Self::BlockNumber>;
+
+	/// Chance that a bred kitty gets one random gene on top of its parents' mix.
+	type MutationChance: Get<Permill>;
+
+	/// Blocks between committing to a DNA secret and the block its DNA is drawn from.
+	type RevealDelay: Get<Self::BlockNumber>;
+	/// Blocks after the reveal block in which a commitment can still be revealed, it is void
+	/// afterwards. Has to stay below `BlockHashCount`, as the hash of the reveal block is needed.
+	type RevealWindow: Get<Self::BlockNumber>;
+	/// Reserved for each pending DNA commitment, refunded on reveal and slashed once it expires.
+	type CommitmentDeposit: Get<BalanceOf<Self>>;
+
+	/// Longest kitty name, in bytes.
+	type MaxNameLength: Get<u32>;
+	/// Longest kitty description, in bytes.
+	type MaxDescriptionLength: Get<u32>;
+	/// Deposit reserved for each byte of metadata stored.
+	type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// Ownership changes kept per kitty, the oldest ones are dropped first.
+	type MaxProvenanceEntries: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
 
+// Kitty dna data, array of u8, length 16, and its breeding state
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Kitty<BlockNumber> {
+	pub dna: [u8; 16],
+	/// 0 for created kitties, the parents' highest generation + 1 for bred ones.
+	pub generation: u16,
+	/// Block the kitty was created or born in.
+	pub birth: BlockNumber,
+	/// The kitty can't breed before this block.
+	pub cooldown_end: BlockNumber,
 }
 
-// Kitty dna data, array of u8, length 16
-#[derive(Encode, Decode)]
-pub struct Kitty(pub [u8; 16]);
+/// Sex of a kitty, breeding needs one parent of each.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Sex {
+	Male,
+	Female,
+}
 
+impl<BlockNumber> Kitty<BlockNumber> {
+	pub fn sex(&self) -> Sex {
+		genes::sex(&self.dna)
+	}
+}
+
+/// Layout versions of the pallet storage, `None` is the original one.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	/// `Kitty` has a generation, birth block and cooldown.
+	V1,
+	/// `FamilyMap` is keyed by `Relation` instead of a string.
+	V2,
+	/// `KittyPrices` records the currency of the price.
+	V3,
+	/// Every kitty records the stake reserved for it in `KittyDeposits`.
+	V4,
+	/// `AccountKitties` is a double map with the number of kitties per owner in `OwnedKittiesCount`.
+	V5,
+}
+
+/// Kind of relatives of a kitty tracked in `FamilyMap`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Relation {
+	Children,
+	Partner,
+}
+
+/// An English auction of a kitty, settled in `on_finalize` of the `end` block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	/// Lowest acceptable bid.
+	pub reserve_price: Balance,
+	pub end: BlockNumber,
+	/// Highest bidder so far, their bid is reserved until they are outbid or the auction settles.
+	pub best_bid: Option<(AccountId, Balance)>,
+}
+
+/// A Dutch auction of a kitty: the price decays linearly from `start_price` to `floor_price`
+/// over `duration` blocks and the first buyer at or above the current price wins.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DutchAuction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub start_price: Balance,
+	pub floor_price: Balance,
+	pub start: BlockNumber,
+	pub duration: BlockNumber,
+}
+
+/// Terms an owner lends a kitty on: `price` for `duration` blocks of use.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RentalOffer<Balance, BlockNumber> {
+	pub price: Balance,
+	pub duration: BlockNumber,
+}
+
+/// A running rental. The renter uses the kitty until `on_initialize` of the `end` block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Rental<AccountId, BlockNumber> {
+	pub renter: AccountId,
+	pub end: BlockNumber,
+}
+
+/// How a kitty changed hands.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TransferKind {
+	/// `transfer`, `transfer_from` or `transfer_batch`, nothing was paid.
+	Gift,
+	/// Bought at its asking price.
+	Sale,
+	/// Won in an English or Dutch auction.
+	Auction,
+	/// Traded through another pallet, such as an exchange order. Its payment isn't recorded.
+	Trade,
+}
+
+/// An ownership change of a kitty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProvenanceEntry<AccountId, CurrencyId, Balance, BlockNumber> {
+	pub from: AccountId,
+	pub to: AccountId,
+	pub block: BlockNumber,
+	pub kind: TransferKind,
+	/// What the new owner paid, `None` for gifts.
+	pub price: Option<(CurrencyId, Balance)>,
+}
+
+/// Name and description (a short bio or an image URI) given to a kitty by its owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct KittyMetadata {
+	pub name: Vec<u8>,
+	pub description: Vec<u8>,
+}
+
+/// Stored metadata with the deposit reserved for it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MetadataRecord<AccountId, Balance> {
+	pub metadata: KittyMetadata,
+	/// Account the deposit is reserved from, it stays with them when the kitty is transferred.
+	pub depositor: AccountId,
+	pub deposit: Balance,
+}
+
+/// A pending commitment to a DNA secret.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DnaCommitment<BlockNumber, Balance> {
+	/// Block whose hash the DNA is drawn from, it can be revealed from the next block on.
+	pub reveal_block: BlockNumber,
+	/// Reserved from the committer until the commitment is revealed or cleared.
+	pub deposit: Balance,
+}
+
+/// Everything a client needs to know about a kitty, as returned by the runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber> {
+	pub id: KittyIndex,
+	pub dna: [u8; 16],
+	pub sex: Sex,
+	pub generation: u16,
+	pub birth: BlockNumber,
+	pub cooldown_end: BlockNumber,
+	pub owner: AccountId,
+	/// Account with usage rights: the renter while the kitty is rented out, the owner otherwise.
+	pub user: AccountId,
+	/// Running rental, if any.
+	pub rental: Option<Rental<AccountId, BlockNumber>>,
+	/// Asking price, `None` if the kitty is not for sale.
+	pub price: Option<(CurrencyId, Balance)>,
+	/// Parents as stored in `KittyParents`, `None` for created (not bred) kitties.
+	pub parents: Option<(KittyIndex, KittyIndex)>,
+	pub metadata: Option<KittyMetadata>,
+}
 
 
 decl_storage! {
 
 	trait Store for Module<T: Trait> as Kitties {
-        pub Kitties get(fn kitties): map hasher(blake2_128_concat) T::KittyIndex => Option<Kitty>;
+        pub Kitties get(fn kitties): map hasher(blake2_128_concat) T::KittyIndex => Option<KittyOf<T>>;
         // Get number of kitties created
         pub KittiesCount get(fn kitties_count): T::KittyIndex;
         pub KittyOwners get(fn kitty_owner): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
-		// Get all kitties belong to an account
-		pub AccountKitties get(fn account_kitty): map hasher(blake2_128_concat) T::AccountId => Vec<T::KittyIndex>;
+		// Stake reserved from the owner for each kitty, moved along with it and refunded on release
+		pub KittyDeposits get(fn kitty_deposit): map hasher(blake2_128_concat) T::KittyIndex => BalanceOf<T>;
+		// Kitties of each account, iterate them with `owned_kitties`
+		pub AccountKitties: double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) T::KittyIndex => ();
+		// Number of kitties of each account
+		pub OwnedKittiesCount get(fn owned_kitties_count): map hasher(blake2_128_concat) T::AccountId => u32;
 
 		// Track kitty's children and partner
-		pub FamilyMap get(fn family_map):  double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) &'static str  => Vec<T::KittyIndex>;
+		pub FamilyMap get(fn family_map):  double_map hasher(blake2_128_concat) T::KittyIndex, hasher(twox_64_concat) Relation  => Vec<T::KittyIndex>;
 		// Track kitty and parents
 		pub KittyParents get(fn kitty_parents):  map hasher(blake2_128_concat) T::KittyIndex => (T::KittyIndex, T::KittyIndex);
 		// Track kitty's sibling
 		pub ParentsChildren get(fn sibling):  map hasher(blake2_128_concat) (T::KittyIndex, T::KittyIndex) => Vec<T::KittyIndex>;
 
-		pub Test: u32;
-		pub KittyPrices get(fn kitty_price): map hasher(blake2_128_concat) T::KittyIndex => Option<BalanceOf<T>>;
+		// Storage layout version, used by `on_runtime_upgrade`
+		StorageVersion build(|_| Some(Releases::V5)): Option<Releases>;
+		// Asking price of kitties for sale and the currency it is in
+		pub KittyPrices get(fn kitty_price): map hasher(blake2_128_concat) T::KittyIndex => Option<(CurrencyIdOf<T>, BalanceOf<T>)>;
+		// Fee to breed with a kitty offered as a sire
+		pub SireOffers get(fn sire_fee): map hasher(blake2_128_concat) T::KittyIndex => Option<BalanceOf<T>>;
+
+		// Running English auctions, a kitty in here is locked
+		pub Auctions get(fn auction): map hasher(blake2_128_concat) T::KittyIndex => Option<AuctionOf<T>>;
+		// Auctions to settle at the end of a block
+		pub AuctionsEndAt get(fn auctions_end_at): map hasher(twox_64_concat) T::BlockNumber => Vec<T::KittyIndex>;
+		// Running Dutch auctions, a kitty in here is locked as well
+		pub DutchAuctions get(fn dutch_auction): map hasher(blake2_128_concat) T::KittyIndex => Option<DutchAuctionOf<T>>;
+		// Kitties held in escrow by another pallet through `NonFungibleAsset::lock`, locked too
+		pub Escrowed get(fn escrowed): map hasher(blake2_128_concat) T::KittyIndex => bool;
+
+		// Account allowed to `transfer_from` a single kitty, cleared when the kitty changes hands
+		pub KittyApprovals get(fn approved): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
+		// Operators allowed to manage all kitties of an owner
+		pub OperatorApprovals get(fn is_approved_for_all): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+		// Mixed into every random value, so that values drawn in the same extrinsic differ
+		DnaNonce: u32;
+		// Reveal block and deposit of each pending DNA commitment, by committer and hash of the secret
+		pub DnaCommitments get(fn dna_commitment): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::Hash => Option<DnaCommitmentOf<T>>;
+
+		// Latest ownership changes of each kitty, oldest first
+		pub Provenance get(fn provenance): map hasher(blake2_128_concat) T::KittyIndex => Vec<ProvenanceEntryOf<T>>;
+
+		// Names and descriptions of kitties, with their deposits
+		pub Metadata get(fn metadata): map hasher(blake2_128_concat) T::KittyIndex => Option<MetadataRecordOf<T>>;
+
+		// Terms kitties are offered for rent on, kept for the next renter until withdrawn
+		pub RentalOffers get(fn rental_offer): map hasher(blake2_128_concat) T::KittyIndex => Option<RentalOfferOf<T>>;
+		// Running rentals, a rented kitty can only be used by its renter and can't change hands
+		pub Rentals get(fn rental): map hasher(blake2_128_concat) T::KittyIndex => Option<RentalOf<T>>;
+		// Rentals to end at the start of a block
+		pub RentalsEndAt get(fn rentals_end_at): map hasher(twox_64_concat) T::BlockNumber => Vec<T::KittyIndex>;
     }
+	add_extra_genesis {
+		/// Generation 0 kitties minted at genesis: owner, DNA and an optional asking price.
+		/// Owners have to be endowed with at least `StakeForKitty` per kitty.
+		config(kitties): Vec<(T::AccountId, [u8; 16], Option<(CurrencyIdOf<T>, BalanceOf<T>)>)>;
+		build(|config: &GenesisConfig<T>| {
+			for (owner, dna, price) in config.kitties.iter() {
+				let kitty_id = Module::<T>::next_kitty_id().expect("too many genesis kitties");
+				let stake = T::StakeForKitty::get();
+				T::Currency::reserve(owner, stake).expect("genesis kitty owner can't pay the stake");
+				Module::<T>::insert_kitty(owner, kitty_id, Module::<T>::new_kitty(*dna, 0), stake);
+				if let Some(price) = price {
+					<KittyPrices<T>>::insert(kitty_id, price);
+				}
+			}
+		});
+	}
 }
 
 decl_error! {
@@ -73,7 +366,44 @@ decl_error! {
 		NotEnoughBalance,
 		CantTransferToSelf,
 		NotForSale,
-		PriceTooLow
+		PriceTooLow,
+		CantBuyOwnKitty,
+		KittyInAuction,
+		AuctionNotExist,
+		InvalidAuctionEnd,
+		TooManyAuctions,
+		AuctionEnded,
+		CantBidOwnAuction,
+		BidTooLow,
+		NotAuctionSeller,
+		AuctionHasBids,
+		KittyCoolingDown,
+		RequireOppositeSex,
+		CantBreedParentAndChild,
+		CantBreedSiblings,
+		CantBreedHalfSiblings,
+		NotOfferedAsSire,
+		DuplicateKitty,
+		NotApproved,
+		CantApproveSelf,
+		NameTooLong,
+		DescriptionTooLong,
+		MetadataNotExist,
+		InvalidDutchAuction,
+		WrongCurrency,
+		KittyRented,
+		NotOfferedForRent,
+		InvalidRentalDuration,
+		TooManyRentals,
+		CommitmentExists,
+		CommitmentNotExist,
+		RevealTooEarly,
+		KittyEscrowed,
+		KittyNotEscrowed,
+		BatchTooLarge,
+		TooManyRelatives,
+		CommitmentExpired,
+		CommitmentNotExpired,
     }
 }
 
@@ -81,12 +411,55 @@ decl_event!(
 	pub enum Event<T> where
 	AccountId = <T as frame_system::Trait>::AccountId,
 	KittyIndex = <T as Trait>::KittyIndex,
-	Balance = BalanceOf<T>
+	Balance = BalanceOf<T>,
+	BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	CurrencyId = CurrencyIdOf<T>,
+	Hash = <T as frame_system::Trait>::Hash,
 	{
         Created(AccountId, KittyIndex),
 		Transfered(AccountId, AccountId, KittyIndex),
+		/// A kitty was burnt by its owner. [owner, kitty_id]
+		Released(AccountId, KittyIndex),
 		StakeForKitty(AccountId, Balance),
 		UnstakeForKitty(AccountId, Balance),
+		/// The owner changed the asking price of a kitty, `None` delists it. [owner, kitty_id, price]
+		KittyPriceSet(AccountId, KittyIndex, Option<(CurrencyId, Balance)>),
+		/// A listed kitty was bought. [seller, buyer, kitty_id, currency_id, price]
+		KittySold(AccountId, AccountId, KittyIndex, CurrencyId, Balance),
+		/// The owner offered a kitty as a sire for a fee, `None` withdraws it. [owner, kitty_id, fee]
+		SireOffered(AccountId, KittyIndex, Option<Balance>),
+		/// A sire fee was paid to breed with someone else's kitty. [payer, sire_owner, sire_id, fee]
+		SireFeePaid(AccountId, AccountId, KittyIndex, Balance),
+		/// An English auction was started. [seller, kitty_id, reserve_price, end]
+		AuctionStarted(AccountId, KittyIndex, Balance, BlockNumber),
+		/// A bid was placed and reserved. [bidder, kitty_id, amount]
+		BidPlaced(AccountId, KittyIndex, Balance),
+		/// The auction ended and the kitty went to the best bidder. [seller, winner, kitty_id, amount]
+		AuctionSettled(AccountId, AccountId, KittyIndex, Balance),
+		/// The auction ended without a winner, the kitty stays with the seller. [kitty_id]
+		AuctionEndedWithoutWinner(KittyIndex),
+		/// The seller cancelled an auction without bids. [kitty_id]
+		AuctionCancelled(KittyIndex),
+		/// A Dutch auction was started. [seller, kitty_id, start_price, floor_price, duration]
+		DutchAuctionStarted(AccountId, KittyIndex, Balance, Balance, BlockNumber),
+		/// An account was allowed to transfer a kitty, `None` revokes it. [owner, spender, kitty_id]
+		Approval(AccountId, Option<AccountId>, KittyIndex),
+		/// An operator was allowed or disallowed to manage all kitties of an owner. [owner, operator, approved]
+		ApprovalForAll(AccountId, AccountId, bool),
+		/// The owner set the metadata of a kitty and reserved its deposit. [owner, kitty_id, deposit]
+		MetadataSet(AccountId, KittyIndex, Balance),
+		/// The metadata of a kitty was removed and its deposit refunded. [owner, kitty_id]
+		MetadataCleared(AccountId, KittyIndex),
+		/// The owner offered a kitty for rent, `None` withdraws the offer. [owner, kitty_id, terms]
+		RentalOffered(AccountId, KittyIndex, Option<RentalOffer<Balance, BlockNumber>>),
+		/// A kitty was rented out. [owner, renter, kitty_id, price, end]
+		Rented(AccountId, AccountId, KittyIndex, Balance, BlockNumber),
+		/// A rental ended and the owner uses the kitty again. [renter, kitty_id]
+		RentalEnded(AccountId, KittyIndex),
+		/// An account committed to a DNA secret. [who, commitment]
+		DnaCommitted(AccountId, Hash),
+		/// A commitment expired without being revealed and its deposit was slashed. [who, commitment]
+		DnaCommitmentCleared(AccountId, Hash),
 		// StakeTransferred(AccountId, AccountId, Balance),
     }
 );
@@ -96,74 +469,508 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
-        #[weight=0]
+        #[weight = T::WeightInfo::create()]
         pub fn create(origin) {
 			let sender = ensure_signed(origin)?;
 
-			Self::reserve(sender.clone(), T::StakeForKitty::get());
-
             let kitty_id = Self::next_kitty_id()?;
+			let stake = T::StakeForKitty::get();
+			Self::reserve(sender.clone(), stake)?;
+
             let dna = Self::random_value(&sender);
-            let kitty = Kitty(dna);
+            let kitty = Self::new_kitty(dna, 0);
 
-            Self::insert_kitty(&sender, kitty_id, kitty);
+            Self::insert_kitty(&sender, kitty_id, kitty, stake);
 
             Self::deposit_event(RawEvent::Created(sender, kitty_id));
         }
 
-        #[weight=0]
+        #[weight = T::WeightInfo::transfer()]
+		#[transactional]
         pub fn transfer(origin, to: T::AccountId, kitty_id: T::KittyIndex) {
 			let sender = ensure_signed(origin)?;
-			Self::transfer_stake(sender.clone(), to.clone(), T::StakeForKitty::get());
 
-			// Have to check if the sender own the kitty
-			let kitty_owner = <KittyOwners<T>>::get(kitty_id)
-								.ok_or(Error::<T>::KittyNotExit)
-								.unwrap();
-			ensure!(sender == kitty_owner, Error::<T>::NotKittyOwner);
-			ensure!(sender != to, Error::<T>::CantTransferToSelf);
-
-			<KittyOwners<T>>::insert(kitty_id, to.clone());
-
-			// Update owner
-			AccountKitties::<T>::mutate(&sender, |val| val.retain(|&x| x != kitty_id));
-
-
-			Self::add_kitty_to_owner(&to, kitty_id);
+			Self::do_transfer(&sender, &to, kitty_id, TransferKind::Gift, None)?;
 
 			Self::deposit_event(RawEvent::Transfered(sender, to, kitty_id));
         }
 
-        #[weight=0]
+        #[weight = T::WeightInfo::breed()]
+		#[transactional]
         pub fn breed(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) {
 			let sender = ensure_signed(origin)?;
-			Self::reserve(sender.clone(), T::StakeForKitty::get());
-            let new_kitty_id = Self::do_breed(&sender, kitty_id_1, kitty_id_2)?;
+			Self::ensure_user(&sender, kitty_id_1)?;
+			Self::ensure_user(&sender, kitty_id_2)?;
+			Self::ensure_unlocked(kitty_id_1)?;
+			Self::ensure_unlocked(kitty_id_2)?;
+			Self::reserve(sender.clone(), T::StakeForKitty::get())?;
+            let new_kitty_id = Self::do_breed(&sender, kitty_id_1, kitty_id_2, None)?;
+			Self::add_kitty_to_owner(&sender, new_kitty_id);
             Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
 		}
 
+		/// First step of `reveal_create` and `reveal_breed`: commit to the hash of a secret and
+		/// reserve `CommitmentDeposit`. The DNA is drawn from the secret and the hash of the block
+		/// `RevealDelay` blocks later, which nobody knows at commit time. The secret can be
+		/// revealed after that block and for `RevealWindow` blocks, the deposit is lost otherwise.
+		#[weight = T::WeightInfo::commit_dna()]
+		pub fn commit_dna(origin, commitment: T::Hash) {
+			let sender = ensure_signed(origin)?;
+			ensure!(!<DnaCommitments<T>>::contains_key(&sender, commitment), Error::<T>::CommitmentExists);
+
+			let deposit = T::CommitmentDeposit::get();
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::NotEnoughBalance)?;
+			let reveal_block = <frame_system::Module<T>>::block_number().saturating_add(T::RevealDelay::get());
+			<DnaCommitments<T>>::insert(&sender, commitment, DnaCommitment { reveal_block, deposit });
+
+			Self::deposit_event(RawEvent::DnaCommitted(sender, commitment));
+		}
+
+		/// `create` with DNA drawn from a secret committed with `commit_dna`.
+		#[weight = T::WeightInfo::reveal_create()]
+		#[transactional]
+		pub fn reveal_create(origin, secret: [u8; 32]) {
+			let sender = ensure_signed(origin)?;
+			let dna = Self::take_commitment(&sender, &secret)?;
+
+			let kitty_id = Self::next_kitty_id()?;
+			let stake = T::StakeForKitty::get();
+			Self::reserve(sender.clone(), stake)?;
+
+			Self::insert_kitty(&sender, kitty_id, Self::new_kitty(dna, 0), stake);
+
+			Self::deposit_event(RawEvent::Created(sender, kitty_id));
+		}
+
+		/// `breed` with the genes mixed by a secret committed with `commit_dna`.
+		#[weight = T::WeightInfo::reveal_breed()]
+		#[transactional]
+		pub fn reveal_breed(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex, secret: [u8; 32]) {
+			let sender = ensure_signed(origin)?;
+			let revealed = Self::take_commitment(&sender, &secret)?;
+			Self::ensure_user(&sender, kitty_id_1)?;
+			Self::ensure_user(&sender, kitty_id_2)?;
+			Self::ensure_unlocked(kitty_id_1)?;
+			Self::ensure_unlocked(kitty_id_2)?;
+
+			Self::reserve(sender.clone(), T::StakeForKitty::get())?;
+			let new_kitty_id = Self::do_breed(&sender, kitty_id_1, kitty_id_2, Some(revealed))?;
+			Self::add_kitty_to_owner(&sender, new_kitty_id);
+
+			Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+		}
+
+		/// Remove a commitment of `who` that was not revealed in time and slash its deposit.
+		/// Anyone can clear expired commitments.
+		#[weight = T::WeightInfo::clear_dna_commitment()]
+		pub fn clear_dna_commitment(origin, who: T::AccountId, commitment: T::Hash) {
+			ensure_signed(origin)?;
+			let pending = Self::dna_commitment(&who, commitment).ok_or(Error::<T>::CommitmentNotExist)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now > Self::reveal_deadline(&pending), Error::<T>::CommitmentNotExpired);
+
+			<DnaCommitments<T>>::remove(&who, commitment);
+			let _ = T::Currency::slash_reserved(&who, pending.deposit);
+
+			Self::deposit_event(RawEvent::DnaCommitmentCleared(who, commitment));
+		}
+
+		/// Allow `spender` to transfer a kitty on the owner's behalf, `None` revokes the approval.
+		/// Operators of the owner can approve as well.
+		#[weight = T::WeightInfo::approve()]
+		pub fn approve(origin, spender: Option<T::AccountId>, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner || Self::is_approved_for_all(&owner, &sender), Error::<T>::NotApproved);
+			ensure!(spender.as_ref() != Some(&owner), Error::<T>::CantApproveSelf);
+
+			<KittyApprovals<T>>::mutate_exists(kitty_id, |approved| *approved = spender.clone());
+
+			Self::deposit_event(RawEvent::Approval(owner, spender, kitty_id));
+		}
+
+		/// Allow or disallow `operator` to approve and transfer all kitties of the sender.
+		#[weight = T::WeightInfo::set_approval_for_all()]
+		pub fn set_approval_for_all(origin, operator: T::AccountId, approved: bool) {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != operator, Error::<T>::CantApproveSelf);
+
+			if approved {
+				<OperatorApprovals<T>>::insert(&sender, &operator, true);
+			} else {
+				<OperatorApprovals<T>>::remove(&sender, &operator);
+			}
+
+			Self::deposit_event(RawEvent::ApprovalForAll(sender, operator, approved));
+		}
+
+		/// Transfer a kitty of `from`, the sender has to be its owner, approved for it or an operator of `from`.
+		#[weight = T::WeightInfo::transfer_from()]
+		#[transactional]
+		pub fn transfer_from(origin, from: T::AccountId, to: T::AccountId, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				sender == from
+					|| Self::approved(kitty_id).as_ref() == Some(&sender)
+					|| Self::is_approved_for_all(&from, &sender),
+				Error::<T>::NotApproved
+			);
+
+			Self::do_transfer(&from, &to, kitty_id, TransferKind::Gift, None)?;
+
+			Self::deposit_event(RawEvent::Transfered(from, to, kitty_id));
+		}
+
+		/// Transfer several kitties at once, either all of them or none.
+		#[weight = T::WeightInfo::transfer_batch(kitty_ids.len() as u32)]
+		#[transactional]
+		pub fn transfer_batch(origin, to: T::AccountId, kitty_ids: Vec<T::KittyIndex>) {
+			let sender = ensure_signed(origin)?;
+
+			Self::do_transfer_batch(&sender, &to, &kitty_ids)?;
+
+			for kitty_id in kitty_ids {
+				Self::deposit_event(RawEvent::Transfered(sender.clone(), to.clone(), kitty_id));
+			}
+		}
+
+		/// Breed several pairs at once, either all of them or none.
+		/// Rented kitties are bred by their renter, who gets the children.
+		#[weight = T::WeightInfo::breed_batch(pairs.len() as u32)]
+		#[transactional]
+		pub fn breed_batch(origin, pairs: Vec<(T::KittyIndex, T::KittyIndex)>) {
+			let sender = ensure_signed(origin)?;
+			ensure!(pairs.len() <= MAX_BATCH_SIZE as usize, Error::<T>::BatchTooLarge);
+			for &(kitty_id_1, kitty_id_2) in pairs.iter() {
+				Self::ensure_user(&sender, kitty_id_1)?;
+				Self::ensure_user(&sender, kitty_id_2)?;
+				Self::ensure_unlocked(kitty_id_1)?;
+				Self::ensure_unlocked(kitty_id_2)?;
+			}
+
+			Self::reserve(sender.clone(), T::StakeForKitty::get().saturating_mul((pairs.len() as u32).into()))?;
+			let mut new_kitty_ids = Vec::with_capacity(pairs.len());
+			for (kitty_id_1, kitty_id_2) in pairs {
+				new_kitty_ids.push(Self::do_breed(&sender, kitty_id_1, kitty_id_2, None)?);
+			}
+			for new_kitty_id in new_kitty_ids {
+				Self::add_kitty_to_owner(&sender, new_kitty_id);
+				Self::deposit_event(RawEvent::Created(sender.clone(), new_kitty_id));
+			}
+		}
+
+		/// Burn a kitty: remove it with its listing, sire and rental offers, approval and metadata, and give its stake back.
+		#[weight = T::WeightInfo::release(FAMILY_WEIGHT_BOUND)]
+		pub fn release(origin, kitty_id: T::KittyIndex) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_owner(&sender, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_not_rented(kitty_id)?;
+
+			let partners = FamilyMap::<T>::decode_len(kitty_id, Relation::Partner).unwrap_or(0) as u32;
+			let stake = <KittyDeposits<T>>::take(kitty_id);
+			Self::remove_kitty(&sender, kitty_id);
+
+			T::Currency::unreserve(&sender, stake);
+
+			Self::deposit_event(RawEvent::UnstakeForKitty(sender.clone(), stake));
+			Self::deposit_event(RawEvent::Released(sender, kitty_id));
+
+			Ok(Some(T::WeightInfo::release(partners)).into())
+		}
+
+		/// Offer a kitty as a sire for `fee`, `None` withdraws the offer.
+		#[weight = T::WeightInfo::offer_sire()]
+		pub fn offer_sire(origin, kitty_id: T::KittyIndex, fee: Option<BalanceOf<T>>) {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_owner(&sender, kitty_id)?;
+			<SireOffers<T>>::mutate_exists(kitty_id, |offer| *offer = fee);
+
+			Self::deposit_event(RawEvent::SireOffered(sender, kitty_id, fee));
+		}
+
+		/// Breed an own or rented matron with someone else's sire, paying its fee as long as it
+		/// doesn't exceed `max_fee`. The child goes to the sender. Rented sires can't be offered.
+		#[weight = T::WeightInfo::breed_with_sire()]
+		#[transactional]
+		pub fn breed_with_sire(origin, matron_id: T::KittyIndex, sire_id: T::KittyIndex, max_fee: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_user(&sender, matron_id)?;
+			let sire_owner = Self::kitty_owner(sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let fee = Self::sire_fee(sire_id).ok_or(Error::<T>::NotOfferedAsSire)?;
+			ensure!(max_fee >= fee, Error::<T>::PriceTooLow);
+			Self::ensure_unlocked(matron_id)?;
+			Self::ensure_unlocked(sire_id)?;
+			Self::ensure_not_rented(sire_id)?;
+
+			Self::reserve(sender.clone(), T::StakeForKitty::get())?;
+			let new_kitty_id = Self::do_breed(&sender, matron_id, sire_id, None)?;
+			Self::add_kitty_to_owner(&sender, new_kitty_id);
+			if sire_owner != sender {
+				T::Currency::transfer(&sender, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+				Self::deposit_event(RawEvent::SireFeePaid(sender.clone(), sire_owner, sire_id, fee));
+			}
+
+			Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+		}
+
+		/// Name a kitty and describe it, reserving `MetadataDepositPerByte` for each byte.
+		/// Any previous metadata is replaced and its deposit refunded.
+		#[weight = T::WeightInfo::set_metadata((name.len() + description.len()) as u32)]
+		#[transactional]
+		pub fn set_metadata(origin, kitty_id: T::KittyIndex, name: Vec<u8>, description: Vec<u8>) {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_owner(&sender, kitty_id)?;
+			ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::NameTooLong);
+			ensure!(description.len() <= T::MaxDescriptionLength::get() as usize, Error::<T>::DescriptionTooLong);
+
+			let bytes = (name.len() + description.len()) as u32;
+			let deposit = T::MetadataDepositPerByte::get().saturating_mul(bytes.into());
+			if let Some(old) = <Metadata<T>>::take(kitty_id) {
+				T::Currency::unreserve(&old.depositor, old.deposit);
+			}
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::NotEnoughBalance)?;
+			<Metadata<T>>::insert(kitty_id, MetadataRecord {
+				metadata: KittyMetadata { name, description },
+				depositor: sender.clone(),
+				deposit,
+			});
+
+			Self::deposit_event(RawEvent::MetadataSet(sender, kitty_id, deposit));
+		}
+
+		/// Remove the metadata of a kitty and refund its deposit.
+		#[weight = T::WeightInfo::clear_metadata()]
+		pub fn clear_metadata(origin, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_owner(&sender, kitty_id)?;
+			ensure!(<Metadata<T>>::contains_key(kitty_id), Error::<T>::MetadataNotExist);
+
+			Self::remove_metadata(kitty_id);
+
+			Self::deposit_event(RawEvent::MetadataCleared(sender, kitty_id));
+		}
+
 		fn on_runtime_upgrade() -> Weight {
-			// Map from old u16 to u32
-			let _ = Test::translate::<_, _>(|value: Option<u16>| value.map(|v| v as u32));
-			0
+			migration::migrate_to_v1::<T>()
+				.saturating_add(migration::migrate_to_v2::<T>())
+				.saturating_add(migration::migrate_to_v3::<T>())
+				.saturating_add(migration::migrate_to_v4::<T>())
+				.saturating_add(migration::migrate_to_v5::<T>())
 		}
 
-		#[weight = 0]
-		pub fn ask(orign, kitty_id: T::KittyIndex, new_price: Option<BalanceOf<T>>) {
+		/// List a kitty for sale at a price in any currency, `None` delists it.
+		#[weight = T::WeightInfo::ask()]
+		pub fn ask(orign, kitty_id: T::KittyIndex, new_price: Option<(CurrencyIdOf<T>, BalanceOf<T>)>) {
 			let sender = ensure_signed(orign)?;
 			ensure!(Self::kitty_owner(&kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_not_rented(kitty_id)?;
 			<KittyPrices<T>>::mutate_exists(kitty_id, |price| *price = new_price);
+
+			Self::deposit_event(RawEvent::KittyPriceSet(sender, kitty_id, new_price));
 		}
 
-		#[weight = 0]
-		pub fn buy(orign, kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) {
+		/// Buy a listed kitty, paying its asking price as long as it is in `currency_id`
+		/// and doesn't exceed `max_price`.
+		#[weight = T::WeightInfo::buy()]
+		#[transactional]
+		pub fn buy(orign, kitty_id: T::KittyIndex, currency_id: CurrencyIdOf<T>, max_price: BalanceOf<T>) {
 			let sender = ensure_signed(orign)?;
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
-			let kitty_price = Self::kitty_price(kitty_id).ok_or(Error::<T>::NotForSale)?;
-			ensure!(price.unwrap() >= kitty_price, Error::<T>::PriceTooLow);
-			T::Currency::transfer(&sender, &owner, kitty_price, ExistenceRequirement::KeepAlive)?;
+			ensure!(sender != owner, Error::<T>::CantBuyOwnKitty);
+			let (price_currency_id, kitty_price) = Self::kitty_price(kitty_id).ok_or(Error::<T>::NotForSale)?;
+			ensure!(currency_id == price_currency_id, Error::<T>::WrongCurrency);
+			ensure!(max_price >= kitty_price, Error::<T>::PriceTooLow);
+
+			T::MultiCurrency::transfer(currency_id, &sender, &owner, kitty_price)?;
+			Self::do_transfer(&owner, &sender, kitty_id, TransferKind::Sale, Some((currency_id, kitty_price)))?;
+
+			Self::deposit_event(RawEvent::KittySold(owner, sender, kitty_id, currency_id, kitty_price));
+		}
+
+		/// Put a kitty up for an English auction ending at block `end`. Any listing is cancelled.
+		#[weight = T::WeightInfo::start_auction()]
+		pub fn start_auction(origin, kitty_id: T::KittyIndex, reserve_price: BalanceOf<T>, end: T::BlockNumber) {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(&kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_not_rented(kitty_id)?;
+			ensure!(end > <frame_system::Module<T>>::block_number(), Error::<T>::InvalidAuctionEnd);
+			ensure!(
+				<AuctionsEndAt<T>>::decode_len(end).unwrap_or(0) < T::MaxAuctionsPerBlock::get() as usize,
+				Error::<T>::TooManyAuctions
+			);
+
+			<KittyPrices<T>>::remove(kitty_id);
+			<Auctions<T>>::insert(kitty_id, Auction {
+				seller: sender.clone(),
+				reserve_price,
+				end,
+				best_bid: None,
+			});
+			<AuctionsEndAt<T>>::append(end, kitty_id);
+
+			Self::deposit_event(RawEvent::AuctionStarted(sender, kitty_id, reserve_price, end));
+		}
+
+		/// Bid on a running auction. The bid is reserved and the previous best bidder refunded.
+		#[weight = T::WeightInfo::bid()]
+		#[transactional]
+		pub fn bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+
+			<Auctions<T>>::try_mutate(kitty_id, |maybe_auction| -> DispatchResult {
+				let auction = maybe_auction.as_mut().ok_or(Error::<T>::AuctionNotExist)?;
+				ensure!(<frame_system::Module<T>>::block_number() <= auction.end, Error::<T>::AuctionEnded);
+				ensure!(sender != auction.seller, Error::<T>::CantBidOwnAuction);
+				ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+				if let Some((_, best)) = &auction.best_bid {
+					ensure!(amount > *best, Error::<T>::BidTooLow);
+				}
+
+				T::Currency::reserve(&sender, amount).map_err(|_| Error::<T>::NotEnoughBalance)?;
+				if let Some((outbid, best)) = auction.best_bid.take() {
+					T::Currency::unreserve(&outbid, best);
+				}
+				auction.best_bid = Some((sender.clone(), amount));
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::BidPlaced(sender, kitty_id, amount));
+		}
+
+		/// Cancel an auction that has not received any bid yet.
+		#[weight = T::WeightInfo::cancel_auction()]
+		pub fn cancel_auction(origin, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auction(kitty_id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(sender == auction.seller, Error::<T>::NotAuctionSeller);
+			ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			<Auctions<T>>::remove(kitty_id);
+			<AuctionsEndAt<T>>::mutate(auction.end, |ids| ids.retain(|&id| id != kitty_id));
+
+			Self::deposit_event(RawEvent::AuctionCancelled(kitty_id));
+		}
+
+		/// Put a kitty up for a Dutch auction. Any listing is cancelled.
+		#[weight = T::WeightInfo::start_dutch_auction()]
+		pub fn start_dutch_auction(
+			origin,
+			kitty_id: T::KittyIndex,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			duration: T::BlockNumber
+		) {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(&kitty_id) == Some(sender.clone()), Error::<T>::NotKittyOwner);
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_not_rented(kitty_id)?;
+			ensure!(start_price >= floor_price && !duration.is_zero(), Error::<T>::InvalidDutchAuction);
+
+			<KittyPrices<T>>::remove(kitty_id);
+			<DutchAuctions<T>>::insert(kitty_id, DutchAuction {
+				seller: sender.clone(),
+				start_price,
+				floor_price,
+				start: <frame_system::Module<T>>::block_number(),
+				duration,
+			});
+
+			Self::deposit_event(RawEvent::DutchAuctionStarted(sender, kitty_id, start_price, floor_price, duration));
+		}
+
+		/// Buy a kitty from a Dutch auction at its current price, as long as it doesn't exceed `max_price`.
+		#[weight = T::WeightInfo::buy_dutch()]
+		#[transactional]
+		pub fn buy_dutch(origin, kitty_id: T::KittyIndex, max_price: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+			let auction = <DutchAuctions<T>>::take(kitty_id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(sender != auction.seller, Error::<T>::CantBuyOwnKitty);
+			let price = Self::dutch_price_at(&auction, <frame_system::Module<T>>::block_number());
+			ensure!(max_price >= price, Error::<T>::PriceTooLow);
+
+			T::Currency::transfer(&sender, &auction.seller, price, ExistenceRequirement::KeepAlive)?;
+			let paid = Some((T::GetNativeCurrencyId::get(), price));
+			Self::do_transfer(&auction.seller, &sender, kitty_id, TransferKind::Auction, paid)?;
+
+			Self::deposit_event(RawEvent::KittySold(auction.seller, sender, kitty_id, T::GetNativeCurrencyId::get(), price));
+		}
+
+		#[weight = T::WeightInfo::cancel_dutch_auction()]
+		pub fn cancel_dutch_auction(origin, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::dutch_auction(kitty_id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(sender == auction.seller, Error::<T>::NotAuctionSeller);
+
+			<DutchAuctions<T>>::remove(kitty_id);
+
+			Self::deposit_event(RawEvent::AuctionCancelled(kitty_id));
+		}
+
+		/// Offer a kitty for rent on the given terms, `None` withdraws the offer.
+		/// A running rental is not affected.
+		#[weight = T::WeightInfo::offer_rental()]
+		pub fn offer_rental(origin, kitty_id: T::KittyIndex, offer: Option<RentalOfferOf<T>>) {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_owner(&sender, kitty_id)?;
+			if let Some(offer) = &offer {
+				ensure!(!offer.duration.is_zero(), Error::<T>::InvalidRentalDuration);
+			}
+			<RentalOffers<T>>::mutate_exists(kitty_id, |current| *current = offer.clone());
+
+			Self::deposit_event(RawEvent::RentalOffered(sender, kitty_id, offer));
+		}
+
+		/// Rent a kitty offered for rent, paying its owner the price as long as it doesn't exceed
+		/// `max_price`. The sender uses the kitty for the offered duration, during which the owner
+		/// can't transfer, list or release it. Any listing is cancelled.
+		#[weight = T::WeightInfo::rent()]
+		#[transactional]
+		pub fn rent(origin, kitty_id: T::KittyIndex, max_price: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender != owner, Error::<T>::CantBuyOwnKitty);
+			let offer = Self::rental_offer(kitty_id).ok_or(Error::<T>::NotOfferedForRent)?;
+			ensure!(max_price >= offer.price, Error::<T>::PriceTooLow);
+			Self::ensure_unlocked(kitty_id)?;
+			Self::ensure_not_rented(kitty_id)?;
+
+			let end = <frame_system::Module<T>>::block_number().saturating_add(offer.duration);
+			ensure!(
+				<RentalsEndAt<T>>::decode_len(end).unwrap_or(0) < T::MaxRentalsPerBlock::get() as usize,
+				Error::<T>::TooManyRentals
+			);
+
+			T::Currency::transfer(&sender, &owner, offer.price, ExistenceRequirement::KeepAlive)?;
+
 			<KittyPrices<T>>::remove(kitty_id);
-			<KittyOwners<T>>::insert(kitty_id, sender.clone());
+			<Rentals<T>>::insert(kitty_id, Rental { renter: sender.clone(), end });
+			<RentalsEndAt<T>>::append(end, kitty_id);
+
+			Self::deposit_event(RawEvent::Rented(owner, sender, kitty_id, offer.price, end));
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expired = <RentalsEndAt<T>>::take(now);
+			for kitty_id in expired.iter() {
+				if let Some(rental) = <Rentals<T>>::take(kitty_id) {
+					Self::deposit_event(RawEvent::RentalEnded(rental.renter, *kitty_id));
+				}
+			}
+
+			// Account for the auctions settled in `on_finalize`
+			let ending = <AuctionsEndAt<T>>::decode_len(now).unwrap_or(0) as u32;
+			T::WeightInfo::on_initialize(expired.len() as u32)
+				.saturating_add(T::WeightInfo::on_finalize(ending))
+		}
+
+		fn on_finalize(now: T::BlockNumber) {
+			for kitty_id in <AuctionsEndAt<T>>::take(now) {
+				Self::settle_auction(kitty_id);
+			}
 		}
     }
 }
@@ -176,14 +983,34 @@ fn combine_dna(dna1: u8, dna2: u8, selector: u8) -> u8 {
 
 impl <T:Trait> Module<T> {
 
-    fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: Kitty) {
-        Kitties::<T>::insert(kitty_id, kitty);
-        KittiesCount::<T>::put(kitty_id + One::one());
-		<KittyOwners<T>>::insert(kitty_id, owner);
+    fn new_kitty(dna: [u8; 16], generation: u16) -> KittyOf<T> {
+		let now = <frame_system::Module<T>>::block_number();
+		Kitty { dna, generation, birth: now, cooldown_end: now }
+	}
 
+	/// Breeding cooldown of a kitty, growing with its generation.
+	pub fn cooldown_of(generation: u16) -> T::BlockNumber {
+		T::BreedCooldown::get().saturating_mul((generation as u32 + 1).into())
+	}
+
+	fn start_cooldown(kitty_id: T::KittyIndex, mut kitty: KittyOf<T>, now: T::BlockNumber) {
+		kitty.cooldown_end = now.saturating_add(Self::cooldown_of(kitty.generation));
+		Kitties::<T>::insert(kitty_id, kitty);
+	}
+
+    fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: KittyOf<T>, deposit: BalanceOf<T>) {
+		Self::store_kitty(owner, kitty_id, kitty, deposit);
 		Self::add_kitty_to_owner(&owner, kitty_id);
     }
 
+	/// `insert_kitty` without the `AccountKitties` update.
+	fn store_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: KittyOf<T>, deposit: BalanceOf<T>) {
+        Kitties::<T>::insert(kitty_id, kitty);
+        KittiesCount::<T>::put(kitty_id + One::one());
+		<KittyOwners<T>>::insert(kitty_id, owner);
+		<KittyDeposits<T>>::insert(kitty_id, deposit);
+	}
+
     fn next_kitty_id() -> sp_std::result::Result<T::KittyIndex, DispatchError> {
 		let  kitty_id = Self::kitties_count();
 
@@ -193,45 +1020,189 @@ impl <T:Trait> Module<T> {
         Ok(kitty_id)
     }
 
-	fn add_kitty_to_owner(owner: &T::AccountId, kitty_id: T::KittyIndex,) {
-		match AccountKitties::<T>::contains_key(&owner) {
-			true => {
-				AccountKitties::<T>::append(owner, kitty_id)
+	/// Move `kitty_id` from `from` to `to`: ownership indexes, stake and any listing. The change
+	/// is recorded in its provenance with what `to` paid for it.
+	#[transactional]
+	fn do_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		kind: TransferKind,
+		price: Option<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		Self::ensure_can_transfer(from, to, kitty_id)?;
+
+		Self::transfer_stake(from, to, Self::kitty_deposit(kitty_id))?;
+		Self::hand_over(to, kitty_id);
+		Self::record_provenance(kitty_id, from, to, kind, price);
+
+		// Update owner
+		Self::remove_kitty_from_owner(from, kitty_id);
+		Self::add_kitty_to_owner(to, kitty_id);
+
+		Ok(())
+	}
+
+	/// `do_transfer` for many kitties, with a single stake move.
+	#[transactional]
+	fn do_transfer_batch(from: &T::AccountId, to: &T::AccountId, kitty_ids: &[T::KittyIndex]) -> DispatchResult {
+		ensure!(kitty_ids.len() <= MAX_BATCH_SIZE as usize, Error::<T>::BatchTooLarge);
+		let mut stake = BalanceOf::<T>::zero();
+		let mut seen = BTreeSet::new();
+		for kitty_id in kitty_ids {
+			ensure!(seen.insert(*kitty_id), Error::<T>::DuplicateKitty);
+			Self::ensure_can_transfer(from, to, *kitty_id)?;
+			stake = stake.saturating_add(Self::kitty_deposit(kitty_id));
+		}
+
+		Self::transfer_stake(from, to, stake)?;
+		for kitty_id in kitty_ids {
+			Self::hand_over(to, *kitty_id);
+			Self::record_provenance(*kitty_id, from, to, TransferKind::Gift, None);
+			Self::remove_kitty_from_owner(from, *kitty_id);
+			Self::add_kitty_to_owner(to, *kitty_id);
+		}
+
+		Ok(())
+	}
+
+	fn ensure_can_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+		// Have to check if the sender own the kitty
+		let kitty_owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::KittyNotExit)?;
+		ensure!(*from == kitty_owner, Error::<T>::NotKittyOwner);
+		ensure!(from != to, Error::<T>::CantTransferToSelf);
+		Self::ensure_unlocked(kitty_id)?;
+		Self::ensure_not_rented(kitty_id)
+	}
+
+	/// Per kitty part of an ownership change, the account indexes and stake are up to the caller.
+	fn hand_over(to: &T::AccountId, kitty_id: T::KittyIndex) {
+		<KittyOwners<T>>::insert(kitty_id, to.clone());
+
+		// A listing, sire or rental offer or approval belongs to the previous owner
+		<KittyPrices<T>>::remove(kitty_id);
+		<SireOffers<T>>::remove(kitty_id);
+		<RentalOffers<T>>::remove(kitty_id);
+		<KittyApprovals<T>>::remove(kitty_id);
+	}
+
+	/// Append an ownership change to the provenance of a kitty, dropping the oldest entries
+	/// beyond `MaxProvenanceEntries`.
+	fn record_provenance(
+		kitty_id: T::KittyIndex,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		kind: TransferKind,
+		price: Option<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	) {
+		let entry = ProvenanceEntry {
+			from: from.clone(),
+			to: to.clone(),
+			block: <frame_system::Module<T>>::block_number(),
+			kind,
+			price,
+		};
+		<Provenance<T>>::mutate(kitty_id, |history| {
+			history.push(entry);
+			let excess = history.len().saturating_sub(T::MaxProvenanceEntries::get() as usize);
+			history.drain(..excess);
+		});
+	}
+
+	fn ensure_owner(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+		let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+		ensure!(*who == owner, Error::<T>::NotKittyOwner);
+		Ok(())
+	}
+
+	/// Like `ensure_owner`, but a rented kitty can only be used by its renter.
+	fn ensure_user(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+		match Self::rental(kitty_id) {
+			Some(rental) => ensure!(*who == rental.renter, Error::<T>::KittyRented),
+			None => Self::ensure_owner(who, kitty_id)?,
+		}
+		Ok(())
+	}
+
+	/// A rented kitty can't change hands, be listed or be released until the rental ends.
+	fn ensure_not_rented(kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(!<Rentals<T>>::contains_key(kitty_id), Error::<T>::KittyRented);
+		Ok(())
+	}
+
+	/// A kitty in a running auction or in escrow can't be transferred, bred or listed.
+	fn ensure_unlocked(kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!<DutchAuctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!Self::escrowed(kitty_id), Error::<T>::KittyEscrowed);
+		Ok(())
+	}
+
+	fn dutch_price_at(auction: &DutchAuctionOf<T>, now: T::BlockNumber) -> BalanceOf<T> {
+		let elapsed = now.saturating_sub(auction.start);
+		if elapsed >= auction.duration {
+			return auction.floor_price;
+		}
+		let decay = Perbill::from_rational_approximation(elapsed, auction.duration)
+			* (auction.start_price - auction.floor_price);
+		auction.start_price - decay
+	}
+
+	/// Current price of a kitty in a Dutch auction, `None` if it isn't in one.
+	pub fn dutch_auction_price(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+		Self::dutch_auction(kitty_id)
+			.map(|auction| Self::dutch_price_at(&auction, <frame_system::Module<T>>::block_number()))
+	}
+
+	/// Hand an ended auction's kitty to the best bidder and pay the seller, or give up and
+	/// refund the bidder if that isn't possible.
+	fn settle_auction(kitty_id: T::KittyIndex) {
+		let auction = match <Auctions<T>>::take(kitty_id) {
+			Some(auction) => auction,
+			None => return,
+		};
+
+		match auction.best_bid {
+			Some((winner, amount)) => match Self::pay_auction(&auction.seller, &winner, kitty_id, amount) {
+				Ok(()) => Self::deposit_event(RawEvent::AuctionSettled(auction.seller, winner, kitty_id, amount)),
+				Err(_) => {
+					T::Currency::unreserve(&winner, amount);
+					Self::deposit_event(RawEvent::AuctionEndedWithoutWinner(kitty_id));
+				}
 			},
-			false => {
-				AccountKitties::<T>::insert(owner, vec![kitty_id])
-			}
+			None => Self::deposit_event(RawEvent::AuctionEndedWithoutWinner(kitty_id)),
 		}
 	}
 
+	#[transactional]
+	fn pay_auction(seller: &T::AccountId, winner: &T::AccountId, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> DispatchResult {
+		let unpaid = T::Currency::repatriate_reserved(winner, seller, amount, BalanceStatus::Free)?;
+		ensure!(unpaid.is_zero(), Error::<T>::NotEnoughBalance);
+		Self::do_transfer(seller, winner, kitty_id, TransferKind::Auction, Some((T::GetNativeCurrencyId::get(), amount)))
+	}
+
+	fn add_kitty_to_owner(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		<AccountKitties<T>>::insert(owner, kitty_id, ());
+		<OwnedKittiesCount<T>>::mutate(owner, |count| *count = count.saturating_add(1));
+	}
+
+	fn remove_kitty_from_owner(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		<AccountKitties<T>>::remove(owner, kitty_id);
+		<OwnedKittiesCount<T>>::mutate_exists(owner, |count| {
+			*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+		});
+	}
+
 	fn add_kitty_to_family_tree(parent_1: T::KittyIndex, parent_2: T::KittyIndex, child:T::KittyIndex) {
 
 
-		for (idx, p) in [parent_1, parent_2].iter().enumerate() {
+		for &(p, partner) in [(parent_1, parent_2), (parent_2, parent_1)].iter() {
 			// Parent add child
+			FamilyMap::<T>::append(p, Relation::Children, child);
 
-			match FamilyMap::<T>::contains_key(p, "Children") {
-				true => {
-					FamilyMap::<T>::mutate(p, "Children",  |children| children.push(child));
-
-				},
-				false => {
-					FamilyMap::<T>::insert(p, "Children", vec![child]);
-				}
-			}
 			// Parent add partner
-			let mut p2 = parent_2;
-			if idx == 1 {
-				p2 = parent_1;
-			}
-			match FamilyMap::<T>::contains_key(p, "Partner") {
-				true => {
-					FamilyMap::<T>::mutate(p, "Partner",  |partner| if !partner.contains(&p2){partner.push(p2)});
-
-				},
-				false => {
-					FamilyMap::<T>::insert(p, "Partner", vec![p2]);
-				}
+			if !FamilyMap::<T>::get(p, Relation::Partner).contains(&partner) {
+				FamilyMap::<T>::append(p, Relation::Partner, partner);
 			}
 		}
 
@@ -240,46 +1211,135 @@ impl <T:Trait> Module<T> {
 		if parent_1 > parent_2 {
 			parents_tuple = (parent_2, parent_1);
 		}
-		<KittyParents<T>>::insert(child, parents_tuple.clone());
+		<KittyParents<T>>::insert(child, parents_tuple);
 
 		// Add parents -> children map
-		if <ParentsChildren<T>>::contains_key(parents_tuple) {
-			<ParentsChildren<T>>::mutate(parents_tuple, |children| children.push(child));
-        } else {
-            <ParentsChildren<T>>::insert(parents_tuple, vec![child]);
-        }
+		<ParentsChildren<T>>::append(parents_tuple, child);
 
 	}
 
 
+	fn remove_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		Kitties::<T>::remove(kitty_id);
+		<KittyOwners<T>>::remove(kitty_id);
+		Self::remove_kitty_from_owner(owner, kitty_id);
+		<KittyPrices<T>>::remove(kitty_id);
+		<SireOffers<T>>::remove(kitty_id);
+		<RentalOffers<T>>::remove(kitty_id);
+		<KittyApprovals<T>>::remove(kitty_id);
+		<Provenance<T>>::remove(kitty_id);
+		Self::remove_metadata(kitty_id);
+
+		Self::remove_from_family_tree(kitty_id);
+	}
+
+	fn remove_metadata(kitty_id: T::KittyIndex) {
+		if let Some(record) = <Metadata<T>>::take(kitty_id) {
+			T::Currency::unreserve(&record.depositor, record.deposit);
+		}
+	}
+
+	/// Drop a removed kitty from its parents' children and its partners' partners. The
+	/// parents recorded for its own children are lineage and stay, so that their breeding
+	/// rules keep working.
+	fn remove_from_family_tree(kitty_id: T::KittyIndex) {
+		if let Some(parents) = Self::parents_of(kitty_id) {
+			for p in [parents.0, parents.1].iter() {
+				FamilyMap::<T>::mutate(p, Relation::Children, |children| children.retain(|&x| x != kitty_id));
+			}
+			<ParentsChildren<T>>::mutate_exists(parents, |children| {
+				if let Some(list) = children {
+					list.retain(|&x| x != kitty_id);
+					if list.is_empty() {
+						*children = None;
+					}
+				}
+			});
+			<KittyParents<T>>::remove(kitty_id);
+		}
+
+		for partner in FamilyMap::<T>::take(kitty_id, Relation::Partner) {
+			FamilyMap::<T>::mutate(partner, Relation::Partner, |partners| partners.retain(|&x| x != kitty_id));
+		}
+		FamilyMap::<T>::remove(kitty_id, Relation::Children);
+	}
+
+	/// Random value for `sender`.
     fn random_value(sender: &T::AccountId) -> [u8; 16] {
+		let nonce = DnaNonce::mutate(|nonce| {
+			*nonce = nonce.wrapping_add(1);
+			*nonce
+		});
         let payload = (
             T::Randomness::random_seed(),
             &sender,
             <frame_system::Module<T>>::extrinsic_index(), //Transaction index
+            nonce,
         );
         // Hash with blake_128, 128 bit
         payload.using_encoded(blake2_128)
     }
 
-    fn do_breed(sender: &T::AccountId, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> sp_std::result::Result<T::KittyIndex, DispatchError>
+	/// Consume the commitment of `sender` to `secret`, refund its deposit and return the DNA it
+	/// draws. Only possible once the reveal block is known and until `RevealWindow` blocks after it.
+	fn take_commitment(sender: &T::AccountId, secret: &[u8; 32]) -> sp_std::result::Result<[u8; 16], DispatchError> {
+		let commitment = T::Hashing::hash_of(secret);
+		let pending = Self::dna_commitment(sender, commitment).ok_or(Error::<T>::CommitmentNotExist)?;
+		let now = <frame_system::Module<T>>::block_number();
+		ensure!(now > pending.reveal_block, Error::<T>::RevealTooEarly);
+		ensure!(now <= Self::reveal_deadline(&pending), Error::<T>::CommitmentExpired);
+
+		<DnaCommitments<T>>::remove(sender, commitment);
+		T::Currency::unreserve(sender, pending.deposit);
+
+		let reveal_hash = <frame_system::Module<T>>::block_hash(pending.reveal_block);
+		Ok((secret, reveal_hash, sender).using_encoded(blake2_128))
+	}
+
+	/// Last block a commitment can be revealed in.
+	fn reveal_deadline(pending: &DnaCommitmentOf<T>) -> T::BlockNumber {
+		pending.reveal_block.saturating_add(T::RevealWindow::get())
+	}
+
+	/// Breed a new kitty for `sender`, who still has to be recorded as its owner in `AccountKitties`
+	/// and must have reserved `StakeForKitty` for it. Genes are picked with `revealed` DNA if there is some.
+    fn do_breed(
+		sender: &T::AccountId,
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+		revealed: Option<[u8; 16]>,
+	) -> sp_std::result::Result<T::KittyIndex, DispatchError>
     {
         let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
         let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
 
         ensure!(kitty_id_1 != kitty_id_2, Error::<T>::RequireDifferentParent);
+        ensure!(kitty1.sex() != kitty2.sex(), Error::<T>::RequireOppositeSex);
+        Self::ensure_not_related(kitty_id_1, kitty_id_2)?;
+        Self::ensure_family_room(kitty_id_1, kitty_id_2)?;
+        Self::ensure_family_room(kitty_id_2, kitty_id_1)?;
+
+        let now = <frame_system::Module<T>>::block_number();
+        ensure!(kitty1.cooldown_end <= now && kitty2.cooldown_end <= now, Error::<T>::KittyCoolingDown);
 
         let kitty_id = Self::next_kitty_id()?;
 
-        let kitty1_dna = kitty1.0;
-        let kitty2_dna = kitty2.0;
-        let selector = Self::random_value(&sender);
+        let kitty1_dna = kitty1.dna;
+        let kitty2_dna = kitty2.dna;
+        let selector = revealed.unwrap_or_else(|| Self::random_value(&sender));
         let mut new_dna = [0u8; 16];
 
         for i in 0..kitty1_dna.len() {
             new_dna[i] = combine_dna(kitty1_dna[i], kitty2_dna[i], selector[i]);
         }
-		Self::insert_kitty(sender, kitty_id, Kitty(new_dna));
+		let mutation_entropy = (selector, b"mutation").using_encoded(blake2_128);
+		genes::mutate(&mut new_dna, &mutation_entropy, T::MutationChance::get());
+		let generation = kitty1.generation.max(kitty2.generation).saturating_add(1);
+		Self::store_kitty(sender, kitty_id, Self::new_kitty(new_dna, generation), T::StakeForKitty::get());
+
+		// Both parents have to rest before breeding again
+		Self::start_cooldown(kitty_id_1, kitty1, now);
+		Self::start_cooldown(kitty_id_2, kitty2, now);
 
 		// Add kitty relationship
 		Self::add_kitty_to_family_tree(kitty_id_1, kitty_id_2, kitty_id);
@@ -289,19 +1349,170 @@ impl <T:Trait> Module<T> {
 
 	}
 
-	fn reserve(account: T::AccountId, amount: BalanceOf<T>) {
-		// Reserve
-		// ensure!(T::Currency::can_reserve(&account, T::StakeForKitty::get()) == true, Error::<T>::NotEnoughBalance);
-		let _ = T::Currency::reserve(&account, amount).map_err(|_| Error::<T>::NotEnoughBalance);
+	/// Parents of a bred kitty, `None` for created ones.
+	pub fn parents_of(kitty_id: T::KittyIndex) -> Option<(T::KittyIndex, T::KittyIndex)> {
+		match <KittyParents<T>>::contains_key(kitty_id) {
+			true => Some(Self::kitty_parents(kitty_id)),
+			false => None,
+		}
+	}
 
-		Self::deposit_event(RawEvent::StakeForKitty(account, amount));
+	/// Reject parent–child, sibling and half-sibling pairs.
+	fn ensure_not_related(kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> DispatchResult {
+		let parents_1 = Self::parents_of(kitty_id_1);
+		let parents_2 = Self::parents_of(kitty_id_2);
+
+		if let Some((a, b)) = parents_1 {
+			ensure!(kitty_id_2 != a && kitty_id_2 != b, Error::<T>::CantBreedParentAndChild);
+		}
+		if let Some((a, b)) = parents_2 {
+			ensure!(kitty_id_1 != a && kitty_id_1 != b, Error::<T>::CantBreedParentAndChild);
+		}
+		if let (Some(p1), Some(p2)) = (parents_1, parents_2) {
+			// Parents are stored sorted, so full siblings have equal tuples
+			ensure!(p1 != p2, Error::<T>::CantBreedSiblings);
+			let shares_parent = p1.0 == p2.0 || p1.0 == p2.1 || p1.1 == p2.0 || p1.1 == p2.1;
+			ensure!(!shares_parent, Error::<T>::CantBreedHalfSiblings);
+		}
+
+		Ok(())
+	}
+
+	/// Family lists are bounded by `FAMILY_WEIGHT_BOUND`: a kitty can't breed once it has that
+	/// many children, or that many partners unless `partner` is one of them.
+	fn ensure_family_room(kitty_id: T::KittyIndex, partner: T::KittyIndex) -> DispatchResult {
+		let bound = FAMILY_WEIGHT_BOUND as usize;
+		let children = FamilyMap::<T>::decode_len(kitty_id, Relation::Children).unwrap_or(0);
+		ensure!(children < bound, Error::<T>::TooManyRelatives);
+		let partners = FamilyMap::<T>::get(kitty_id, Relation::Partner);
+		ensure!(partners.len() < bound || partners.contains(&partner), Error::<T>::TooManyRelatives);
+		Ok(())
+	}
+
+	fn reserve(account: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		T::Currency::reserve(&account, amount).map_err(|_| Error::<T>::NotEnoughBalance)?;
 
+		Self::deposit_event(RawEvent::StakeForKitty(account, amount));
+		Ok(())
 	}
 
-	fn transfer_stake(from: T::AccountId, to: T::AccountId, amount: BalanceOf<T>) {
-		T::Currency::unreserve(&from, amount);
-		let _ = T::Currency::transfer(&from, &to, amount, ExistenceRequirement::KeepAlive);
-		let _ =  T::Currency::reserve(&to, amount);
+	/// Move a reserved stake to `to`, who keeps it reserved. Callers have to be transactional.
+	fn transfer_stake(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		// Stakes can only be repatriated to existing accounts, new ones get it free and reserve it
+		if T::Currency::total_balance(to).is_zero() {
+			ensure!(T::Currency::unreserve(from, amount).is_zero(), Error::<T>::NotEnoughBalance);
+			T::Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)?;
+			T::Currency::reserve(to, amount).map_err(|_| Error::<T>::NotEnoughBalance)?;
+			return Ok(());
+		}
+		let unpaid = T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Reserved)?;
+		ensure!(unpaid.is_zero(), Error::<T>::NotEnoughBalance);
 		// Self::deposit_event(RawEvent::StakeTransferred(from, to, amount));
+		Ok(())
+	}
+
+	/// Account with usage rights on a kitty: its renter while rented out, its owner otherwise.
+	pub fn kitty_user(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+		match Self::rental(kitty_id) {
+			Some(rental) => Some(rental.renter),
+			None => Self::kitty_owner(kitty_id),
+		}
+	}
+
+	/// Collect the queryable information of a kitty, `None` if it doesn't exist.
+	pub fn kitty_info(kitty_id: T::KittyIndex) -> Option<KittyInfoOf<T>> {
+		let kitty = Self::kitties(kitty_id)?;
+		let owner = Self::kitty_owner(kitty_id)?;
+		let rental = Self::rental(kitty_id);
+
+		Some(KittyInfo {
+			id: kitty_id,
+			sex: kitty.sex(),
+			dna: kitty.dna,
+			generation: kitty.generation,
+			birth: kitty.birth,
+			cooldown_end: kitty.cooldown_end,
+			user: rental.as_ref().map_or_else(|| owner.clone(), |rental| rental.renter.clone()),
+			owner,
+			rental,
+			price: Self::kitty_price(kitty_id),
+			parents: Self::parents_of(kitty_id),
+			metadata: Self::metadata(kitty_id).map(|record| record.metadata),
+		})
+	}
+
+	/// Visible traits of a kitty, `None` if it doesn't exist.
+	pub fn phenotype(kitty_id: T::KittyIndex) -> Option<Phenotype> {
+		Self::kitties(kitty_id).map(|kitty| decode_dna(&kitty.dna))
+	}
+
+	/// Ids of the kitties of `owner`, read lazily in storage order rather than by id.
+	pub fn owned_kitties(owner: &T::AccountId) -> impl Iterator<Item = T::KittyIndex> {
+		<AccountKitties<T>>::iter_prefix(owner).map(|(kitty_id, _)| kitty_id)
+	}
+
+	/// Kitties of `owner`, skipping the first `start` and returning at most `limit` of them.
+	/// Pages follow `owned_kitties` order, which stays stable while the owner's kitties don't change.
+	pub fn kitties_of_owner(owner: &T::AccountId, start: u32, limit: u32) -> Vec<KittyInfoOf<T>> {
+		Self::owned_kitties(owner)
+			.skip(start as usize)
+			.take(limit.min(MAX_PAGE_SIZE) as usize)
+			.filter_map(Self::kitty_info)
+			.collect()
+	}
+
+	/// Kitties that currently have an asking price, paginated like `kitties_of_owner`.
+	pub fn kitties_for_sale(start: u32, limit: u32) -> Vec<KittyInfoOf<T>> {
+		<KittyPrices<T>>::iter()
+			.skip(start as usize)
+			.take(limit.min(MAX_PAGE_SIZE) as usize)
+			.filter_map(|(kitty_id, _)| Self::kitty_info(kitty_id))
+			.collect()
+	}
+}
+
+impl<T: Trait> NonFungibleAsset<T::AccountId, CurrencyIdOf<T>, BalanceOf<T>> for Module<T> {
+	type AssetId = T::KittyIndex;
+
+	fn owner(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+		Self::kitty_owner(kitty_id)
+	}
+
+	fn is_locked(kitty_id: T::KittyIndex) -> bool {
+		Self::escrowed(kitty_id)
+	}
+
+	/// Escrow a kitty, taking it off sale. It can't be locked while in an auction or rented.
+	fn lock(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+		Self::ensure_owner(who, kitty_id)?;
+		Self::ensure_unlocked(kitty_id)?;
+		Self::ensure_not_rented(kitty_id)?;
+
+		<KittyPrices<T>>::remove(kitty_id);
+		<Escrowed<T>>::insert(kitty_id, true);
+		Ok(())
+	}
+
+	fn unlock(kitty_id: T::KittyIndex) -> DispatchResult {
+		ensure!(Self::escrowed(kitty_id), Error::<T>::KittyNotEscrowed);
+		<Escrowed<T>>::remove(kitty_id);
+		Ok(())
+	}
+
+	fn transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		price: Option<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		Self::do_transfer(from, to, kitty_id, TransferKind::Trade, price)
+	}
+
+	/// A generation 0 kitty without a stake.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn mint_for_benchmarks(owner: &T::AccountId) -> Result<T::KittyIndex, DispatchError> {
+		let kitty_id = Self::next_kitty_id()?;
+		Self::insert_kitty(owner, kitty_id, Self::new_kitty([0u8; 16], 0), Zero::zero());
+		Ok(kitty_id)
 	}
 }
@@ -0,0 +1,172 @@
+//! Storage migrations of the kitties pallet, run from `on_runtime_upgrade`.
+
+use super::*;
+use frame_support::storage::migration::{remove_storage_prefix, StorageIterator};
+use sp_runtime::traits::SaturatedConversion;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// `Kitty` before generations and cooldowns were added.
+#[derive(Decode)]
+struct OldKitty([u8; 16]);
+
+/// Give every kitty a generation, birth block and cooldown, and drop the leftover `Test` value.
+pub fn migrate_to_v1<T: Trait>() -> Weight {
+	if StorageVersion::get().is_some() {
+		return 0;
+	}
+
+	// History before this upgrade is unknown, so every kitty is considered born at genesis
+	Kitties::<T>::translate::<OldKitty, _>(|_, old| Some(Kitty {
+		dna: old.0,
+		generation: 0,
+		birth: Zero::zero(),
+		cooldown_end: Zero::zero(),
+	}));
+
+	// Parents always have a lower index than their children, so walking the kitties in index
+	// order sets every parent's generation before it is needed by a child.
+	let count = Module::<T>::kitties_count();
+	let mut kitty_id = T::KittyIndex::zero();
+	while kitty_id < count {
+		if KittyParents::<T>::contains_key(kitty_id) {
+			let (parent_1, parent_2) = KittyParents::<T>::get(kitty_id);
+			let generation_of = |id: T::KittyIndex| Kitties::<T>::get(id).map(|kitty| kitty.generation).unwrap_or(0);
+			let generation = generation_of(parent_1).max(generation_of(parent_2)).saturating_add(1);
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(kitty) = kitty {
+					kitty.generation = generation;
+				}
+			});
+		}
+		kitty_id += One::one();
+	}
+
+	remove_storage_prefix(b"Kitties", b"Test", &[]);
+	StorageVersion::put(Releases::V1);
+
+	let count: Weight = count.saturated_into();
+	T::DbWeight::get().reads_writes(4 * count + 2, 2 * count + 2)
+}
+
+/// Re-key `FamilyMap` from the `"Children"`/`"Partner"` strings to `Relation`.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+	if StorageVersion::get() != Some(Releases::V1) {
+		return 0;
+	}
+
+	// Old and new keys share the storage prefix, so take all old entries out first
+	let entries = StorageIterator::<Vec<T::KittyIndex>>::new(b"Kitties", b"FamilyMap")
+		.drain()
+		.collect::<Vec<_>>();
+	let count = entries.len() as Weight;
+
+	for (key, relatives) in entries {
+		if let Some((kitty_id, relation)) = decode_legacy_family_key::<T>(&key) {
+			FamilyMap::<T>::insert(kitty_id, relation, relatives);
+		}
+	}
+
+	StorageVersion::put(Releases::V2);
+
+	T::DbWeight::get().reads_writes(count + 1, 2 * count + 1)
+}
+
+/// Price every existing listing in the native currency.
+pub fn migrate_to_v3<T: Trait>() -> Weight {
+	if StorageVersion::get() != Some(Releases::V2) {
+		return 0;
+	}
+
+	let native = T::GetNativeCurrencyId::get();
+	let mut count: Weight = 0;
+	KittyPrices::<T>::translate::<BalanceOf<T>, _>(|_, price| {
+		count += 1;
+		Some((native, price))
+	});
+
+	StorageVersion::put(Releases::V3);
+
+	T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
+
+/// Record the stake still reserved for every existing kitty as its deposit.
+///
+/// Sales used to move a kitty without its stake, so an owner is only credited with what they
+/// actually have reserved, minus their metadata deposits and best bids. Each kitty gets
+/// `StakeForKitty` out of that until it runs out, and the rest get a deposit of zero.
+pub fn migrate_to_v4<T: Trait>() -> Weight {
+	if StorageVersion::get() != Some(Releases::V3) {
+		return 0;
+	}
+
+	let mut reads: Weight = 1;
+	let mut writes: Weight = 1;
+
+	// Reserves that are not kitty stakes
+	let mut claimed = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+	for (_, record) in Metadata::<T>::iter() {
+		let other = claimed.entry(record.depositor).or_insert_with(Zero::zero);
+		*other = other.saturating_add(record.deposit);
+		reads += 1;
+	}
+	for (_, auction) in Auctions::<T>::iter() {
+		if let Some((bidder, bid)) = auction.best_bid {
+			let other = claimed.entry(bidder).or_insert_with(Zero::zero);
+			*other = other.saturating_add(bid);
+		}
+		reads += 1;
+	}
+
+	let stake = T::StakeForKitty::get();
+	let mut available = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+	for (kitty_id, owner) in KittyOwners::<T>::iter() {
+		let left = available.entry(owner.clone()).or_insert_with(|| {
+			reads += 1;
+			let other = claimed.get(&owner).copied().unwrap_or_else(Zero::zero);
+			T::Currency::reserved_balance(&owner).saturating_sub(other)
+		});
+		let deposit = stake.min(*left);
+		*left = left.saturating_sub(deposit);
+		KittyDeposits::<T>::insert(kitty_id, deposit);
+		reads += 1;
+		writes += 1;
+	}
+
+	StorageVersion::put(Releases::V4);
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Rebuild `AccountKitties` as a double map from `KittyOwners`, and count the kitties of every owner.
+pub fn migrate_to_v5<T: Trait>() -> Weight {
+	if StorageVersion::get() != Some(Releases::V4) {
+		return 0;
+	}
+
+	// Old and new keys share the storage prefix, so clear the old lists first
+	remove_storage_prefix(b"Kitties", b"AccountKitties", &[]);
+
+	let mut count: Weight = 0;
+	for (kitty_id, owner) in KittyOwners::<T>::iter() {
+		AccountKitties::<T>::insert(&owner, kitty_id, ());
+		OwnedKittiesCount::<T>::mutate(&owner, |owned| *owned = owned.saturating_add(1));
+		count += 1;
+	}
+
+	StorageVersion::put(Releases::V5);
+
+	T::DbWeight::get().reads_writes(2 * count + 1, 2 * count + 2)
+}
+
+/// Split a `blake2_128_concat(kitty_id) ++ blake2_128_concat(name)` key of the old `FamilyMap`.
+fn decode_legacy_family_key<T: Trait>(key: &[u8]) -> Option<(T::KittyIndex, Relation)> {
+	let mut input = key.get(16..)?;
+	let kitty_id = T::KittyIndex::decode(&mut input).ok()?;
+	let mut input = input.get(16..)?;
+	let relation = match Vec::<u8>::decode(&mut input).ok()?.as_slice() {
+		b"Children" => Relation::Children,
+		b"Partner" => Relation::Partner,
+		_ => return None,
+	};
+	Some((kitty_id, relation))
+}
@@ -3,10 +3,11 @@ use sp_core::H256;
 use frame_support::{impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types,
 	weights::Weight, traits::{OnInitialize, OnFinalize}};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill, Permill,
 };
 use frame_system as system;
 use pallet_balances;
+use orml_currencies::BasicCurrencyAdapter;
 
 
 mod kitty_event {
@@ -22,6 +23,8 @@ impl_outer_event! {
         system<T>,
         kitty_event<T>,
         pallet_balances<T>,
+        orml_tokens<T>,
+        orml_currencies<T>,
     }
 }
 
@@ -77,9 +80,44 @@ impl pallet_balances::Trait for Test {
     type WeightInfo = ();
 }
 
+pub type CurrencyId = u32;
+pub const NATIVE: CurrencyId = 0;
+pub const DOT: CurrencyId = 1;
+
+impl orml_tokens::Trait for Test {
+    type Event = TestEvent;
+    type Balance = u64;
+    type Amount = i64;
+    type CurrencyId = CurrencyId;
+    type OnReceived = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const GetNativeCurrencyId: CurrencyId = NATIVE;
+}
+
+impl orml_currencies::Trait for Test {
+    type Event = TestEvent;
+    type MultiCurrency = orml_tokens::Module<Test>;
+    type NativeCurrency = BasicCurrencyAdapter<Test, pallet_balances::Module<Test>, i64, u64>;
+    type GetNativeCurrencyId = GetNativeCurrencyId;
+    type WeightInfo = ();
+}
 
 parameter_types! {
     pub const StakeForKitty: u32 = 1_000_000;
+    pub const MaxAuctionsPerBlock: u32 = 2;
+    pub const MaxRentalsPerBlock: u32 = 2;
+    pub const BreedCooldown: u64 = 5;
+    pub const MutationChance: Permill = Permill::zero();
+    pub const RevealDelay: u64 = 3;
+    pub const RevealWindow: u64 = 5;
+    pub const CommitmentDeposit: u64 = 100;
+    pub const MaxNameLength: u32 = 8;
+    pub const MaxDescriptionLength: u32 = 32;
+    pub const MetadataDepositPerByte: u64 = 10;
+    pub const MaxProvenanceEntries: u32 = 3;
 }
 
 type Randomness = pallet_randomness_collective_flip::Module<Test>;
@@ -89,11 +127,27 @@ impl Trait for Test {
 	type KittyIndex = u32;
 
 	type StakeForKitty = StakeForKitty;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MaxRentalsPerBlock = MaxRentalsPerBlock;
     type Currency = pallet_balances::Module<Self>;
+    type MultiCurrency = orml_currencies::Module<Test>;
+    type GetNativeCurrencyId = GetNativeCurrencyId;
+    type BreedCooldown = BreedCooldown;
+    type MutationChance = MutationChance;
+    type RevealDelay = RevealDelay;
+    type RevealWindow = RevealWindow;
+    type CommitmentDeposit = CommitmentDeposit;
+    type MaxNameLength = MaxNameLength;
+    type MaxDescriptionLength = MaxDescriptionLength;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type MaxProvenanceEntries = MaxProvenanceEntries;
+    type WeightInfo = ();
 }
 
 pub type Kitties = Module<Test>;
 pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Tokens = orml_tokens::Module<Test>;
 
 pub fn run_to_block(n: u64) {
     while System::block_number() < n {
@@ -107,10 +161,24 @@ pub fn run_to_block(n: u64) {
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    test_ext_from(test_storage())
+}
+
+// `new_test_ext` with kitties minted in genesis.
+pub fn new_test_ext_with_kitties(kitties: Vec<(u64, [u8; 16], Option<(u32, u64)>)>) -> sp_io::TestExternalities {
+    let mut t = test_storage();
+
+    crate::GenesisConfig::<Test> { kitties }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    test_ext_from(t)
+}
+
+fn test_storage() -> sp_runtime::Storage {
     let mut t = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into();
+        .unwrap();
 
     pallet_balances::GenesisConfig::<Test> {
         balances: vec![(1, 1_000_000_000), (2, 1_000_000_000), (3, 1_000_000_000), (4, 1_000_000_000), (5, 1_000_000_000)],
@@ -118,6 +186,16 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .assimilate_storage(&mut t)
     .unwrap();
 
+    orml_tokens::GenesisConfig::<Test> {
+        endowed_accounts: vec![(1, DOT, 1_000_000), (2, DOT, 1_000_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    t
+}
+
+fn test_ext_from(t: sp_runtime::Storage) -> sp_io::TestExternalities {
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
     ext
@@ -0,0 +1,150 @@
+//! Ancestry queries over `KittyParents`: pedigrees, common ancestors and inbreeding.
+//!
+//! A child always has a higher index than its parents, which lets the kinship recursion below
+//! expand the younger kitty of a pair without looking at birth blocks.
+
+use super::*;
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque};
+
+/// Deepest pedigree returned or searched, in generations.
+pub const MAX_PEDIGREE_DEPTH: u32 = 8;
+
+const BILLION: u32 = 1_000_000_000;
+
+/// A kitty in a pedigree.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PedigreeNode<KittyIndex> {
+	pub id: KittyIndex,
+	/// Generations between this kitty and the queried one, 0 for the queried kitty itself.
+	pub depth: u32,
+	/// `None` for created kitties. The parents of the deepest nodes are not nodes themselves.
+	pub parents: Option<(KittyIndex, KittyIndex)>,
+}
+
+/// Nearest ancestor shared by two kitties, which may be one of the kitties itself.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CommonAncestor<KittyIndex> {
+	pub id: KittyIndex,
+	/// Generations from the first kitty up to the ancestor.
+	pub distance_1: u32,
+	/// Generations from the second kitty up to the ancestor.
+	pub distance_2: u32,
+}
+
+type ParentsMap<K> = BTreeMap<K, Option<(K, K)>>;
+
+impl<T: Trait> Module<T> {
+	/// Pedigree of a kitty up to `depth` generations, capped at `MAX_PEDIGREE_DEPTH`. Nodes come
+	/// breadth first and every ancestor is listed once, at its nearest depth, so parents are
+	/// resolved by id. Empty if the kitty doesn't exist.
+	pub fn pedigree(kitty_id: T::KittyIndex, depth: u32) -> Vec<PedigreeNode<T::KittyIndex>> {
+		if !Kitties::<T>::contains_key(kitty_id) {
+			return Vec::new();
+		}
+		let depth = depth.min(MAX_PEDIGREE_DEPTH);
+
+		let mut nodes = Vec::new();
+		let mut seen = BTreeSet::new();
+		let mut queue = VecDeque::new();
+		seen.insert(kitty_id);
+		queue.push_back((kitty_id, 0));
+		while let Some((id, node_depth)) = queue.pop_front() {
+			let parents = Self::parents_of(id);
+			match parents {
+				Some((parent_1, parent_2)) if node_depth < depth => {
+					for parent in [parent_1, parent_2].iter() {
+						if seen.insert(*parent) {
+							queue.push_back((*parent, node_depth + 1));
+						}
+					}
+				},
+				_ => {},
+			}
+			nodes.push(PedigreeNode { id, depth: node_depth, parents });
+		}
+		nodes
+	}
+
+	/// Nearest ancestor of both kitties within `MAX_PEDIGREE_DEPTH` generations, by total
+	/// distance and then lowest id. `None` if they are unrelated or one doesn't exist.
+	pub fn common_ancestor(
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+	) -> Option<CommonAncestor<T::KittyIndex>> {
+		let distances_2 = Self::pedigree(kitty_id_2, MAX_PEDIGREE_DEPTH)
+			.into_iter()
+			.map(|node| (node.id, node.depth))
+			.collect::<BTreeMap<_, _>>();
+
+		Self::pedigree(kitty_id_1, MAX_PEDIGREE_DEPTH)
+			.into_iter()
+			.filter_map(|node| distances_2.get(&node.id).map(|distance_2| CommonAncestor {
+				id: node.id,
+				distance_1: node.depth,
+				distance_2: *distance_2,
+			}))
+			.min_by_key(|ancestor| (ancestor.distance_1 + ancestor.distance_2, ancestor.id))
+	}
+
+	/// Wright's inbreeding coefficient of a kitty: the chance that both copies of a gene come
+	/// from the same ancestor. `None` if the kitty doesn't exist.
+	pub fn inbreeding_coefficient(kitty_id: T::KittyIndex) -> Option<Perbill> {
+		Self::kitties(kitty_id)?;
+		let coefficient = match Self::parents_of(kitty_id) {
+			Some((parent_1, parent_2)) => Self::pairing_inbreeding_coefficient(parent_1, parent_2)?,
+			None => Perbill::zero(),
+		};
+		Some(coefficient)
+	}
+
+	/// Inbreeding coefficient a child of the two kitties would have, for planning pairings.
+	/// `None` if one of them doesn't exist.
+	pub fn pairing_inbreeding_coefficient(kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> Option<Perbill> {
+		Self::kitties(kitty_id_1)?;
+		Self::kitties(kitty_id_2)?;
+
+		let mut parents = ParentsMap::new();
+		for kitty_id in [kitty_id_1, kitty_id_2].iter() {
+			for node in Self::pedigree(*kitty_id, MAX_PEDIGREE_DEPTH) {
+				parents.insert(node.id, node.parents);
+			}
+		}
+
+		let kinship = Self::kinship(kitty_id_1, kitty_id_2, &parents, &mut BTreeMap::new());
+		Some(Perbill::from_parts(kinship))
+	}
+
+	/// Coefficient of coancestry in billionths: the chance that a gene picked from each kitty is
+	/// identical by descent. Kitties missing from `parents` count as unrelated founders.
+	fn kinship(
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+		parents: &ParentsMap<T::KittyIndex>,
+		memo: &mut BTreeMap<(T::KittyIndex, T::KittyIndex), u32>,
+	) -> u32 {
+		let (older, younger) = if kitty_id_1 <= kitty_id_2 {
+			(kitty_id_1, kitty_id_2)
+		} else {
+			(kitty_id_2, kitty_id_1)
+		};
+		if let Some(kinship) = memo.get(&(older, younger)) {
+			return *kinship;
+		}
+
+		let parents_of = |kitty_id| parents.get(&kitty_id).copied().flatten();
+		let kinship = match (older == younger, parents_of(younger)) {
+			// Half of 1 + the kitty's own inbreeding
+			(true, Some((parent_1, parent_2))) => (BILLION + Self::kinship(parent_1, parent_2, parents, memo)) / 2,
+			(true, None) => BILLION / 2,
+			// The younger kitty can't be an ancestor of the older one, so go up its side
+			(false, Some((parent_1, parent_2))) =>
+				(Self::kinship(parent_1, older, parents, memo) + Self::kinship(parent_2, older, parents, memo)) / 2,
+			(false, None) => 0,
+		};
+
+		memo.insert((older, younger), kinship);
+		kinship
+	}
+}
@@ -1,5 +1,24 @@
 use crate::{Error, Event, mock::*};
 use frame_support::{assert_ok, assert_noop, assert_err};
+use crate::{Sex, Relation, DnaCommitment};
+
+// DNA is random, so tests pick the sexes they need
+fn set_sex(kitty_id: u32, sex: Sex) {
+	crate::Kitties::<Test>::mutate(kitty_id, |kitty| {
+		let kitty = kitty.as_mut().unwrap();
+		kitty.dna[0] = match sex {
+			Sex::Male => kitty.dna[0] & !1,
+			Sex::Female => kitty.dna[0] | 1,
+		};
+	});
+}
+
+// Kitties of an account by id, `owned_kitties` iterates in storage order
+fn owned_kitties(owner: u64) -> Vec<u32> {
+	let mut kitty_ids = Kitties::owned_kitties(&owner).collect::<Vec<_>>();
+	kitty_ids.sort();
+	kitty_ids
+}
 
 #[test]
 fn owned_kitties_can_append_values() {
@@ -32,12 +51,32 @@ fn transfer_kitty_success() {
 		assert_eq!(Kitties::create(Origin::signed(1),), Ok(()));
 		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
 		assert_eq!(
-            System::events()[6].event,
+            System::events().last().unwrap().event,
             TestEvent::kitty_event(Event::<Test>::Transfered(1, 2, 0))
         );
 	})
 }
 #[test]
+fn transfer_kitty_to_new_account() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Currency;
+
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 9, 0));
+		assert_eq!(Kitties::kitty_owner(0), Some(9));
+		assert_eq!(Balances::reserved_balance(9), 1_000_000);
+		assert_eq!(Balances::free_balance(9), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 1_000_000);
+
+		assert_ok!(Kitties::transfer(Origin::signed(9), 1, 0));
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+		assert_eq!(Balances::total_balance(&9), 0);
+	})
+}
+#[test]
 fn transfer_kitty_failed_not_owner() {
 	new_test_ext().execute_with(|| {
 		run_to_block(10);
@@ -92,6 +131,8 @@ fn breed_kitty_success() {
 		assert_eq!(Kitties::create(Origin::signed(1),), Ok(()));
 
 		assert_eq!(Kitties::create(Origin::signed(1),), Ok(()));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
 
 		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
 		assert_eq!(
@@ -133,3 +174,1636 @@ fn breed_kitty_failed_same_parent() {
 
 	})
 }
+
+#[test]
+fn kitty_info_reports_owner_price_and_parents() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+		assert_ok!(Kitties::ask(Origin::signed(1), 2, Some((NATIVE, 500))));
+
+		let info = Kitties::kitty_info(2).unwrap();
+		assert_eq!(info.owner, 1);
+		assert_eq!(info.price, Some((NATIVE, 500)));
+		assert_eq!(info.parents, Some((0, 1)));
+
+		assert_eq!(Kitties::kitty_info(0).unwrap().parents, None);
+		assert_eq!(Kitties::kitty_info(3), None);
+	})
+}
+
+#[test]
+fn kitties_of_owner_and_for_sale_are_paginated() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		let ids = |infos: Vec<crate::KittyInfo<u64, u32, u32, u64, u64>>| infos.into_iter().map(|i| i.id).collect::<Vec<_>>();
+		let all = ids(Kitties::kitties_of_owner(&1, 0, 10));
+		assert_eq!(all.len(), 3);
+		assert_eq!(ids(Kitties::kitties_of_owner(&1, 1, 1)), vec![all[1]]);
+		assert_eq!([ids(Kitties::kitties_of_owner(&1, 0, 2)), ids(Kitties::kitties_of_owner(&1, 2, 2))].concat(), all);
+		assert_eq!(owned_kitties(1), vec![0, 2, 3]);
+		assert_eq!(ids(Kitties::kitties_of_owner(&2, 0, 10)), vec![1]);
+
+		assert!(Kitties::kitties_for_sale(0, 10).is_empty());
+		assert_ok!(Kitties::ask(Origin::signed(2), 1, Some((NATIVE, 100))));
+		assert_eq!(ids(Kitties::kitties_for_sale(0, 10)), vec![1]);
+	})
+}
+
+#[test]
+fn buy_kitty_success() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::buy(Origin::signed(2), 0, NATIVE, 1500));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::KittySold(1, 2, 0, NATIVE, 1000))
+		);
+		assert_eq!(Kitties::kitty_owner(0), Some(2));
+		assert_eq!(owned_kitties(1), Vec::<u32>::new());
+		assert_eq!(owned_kitties(2), vec![0]);
+		assert_eq!(Kitties::kitty_price(0), None);
+
+		// The stake moved along with the kitty
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 1_000_000);
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 1_000_000 + 1000);
+		assert_eq!(Balances::free_balance(2), 1_000_000_000 - 1000);
+	})
+}
+
+#[test]
+fn buy_kitty_failed_price_too_low() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+
+		assert_noop!(
+			Kitties::buy(Origin::signed(2), 0, NATIVE, 999),
+			Error::<Test>::PriceTooLow
+		);
+	})
+}
+
+#[test]
+fn buy_kitty_failed_not_for_sale() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, None));
+
+		assert_noop!(
+			Kitties::buy(Origin::signed(2), 0, NATIVE, 1000),
+			Error::<Test>::NotForSale
+		);
+		assert_noop!(
+			Kitties::buy(Origin::signed(1), 0, NATIVE, 1000),
+			Error::<Test>::CantBuyOwnKitty
+		);
+	})
+}
+
+#[test]
+fn buy_kitty_failed_insufficient_balance_changes_nothing() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 2_000_000_000))));
+
+		assert!(Kitties::buy(Origin::signed(2), 0, NATIVE, 2_000_000_000).is_err());
+		assert_eq!(Kitties::kitty_owner(0), Some(1));
+		assert_eq!(Kitties::kitty_price(0), Some((NATIVE, 2_000_000_000)));
+	})
+}
+
+#[test]
+fn transfer_kitty_cancels_listing() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+
+		assert_eq!(Kitties::kitty_price(0), None);
+		assert_eq!(owned_kitties(1), Vec::<u32>::new());
+		assert_eq!(owned_kitties(2), vec![0]);
+	})
+}
+
+#[test]
+fn auction_settles_to_best_bidder() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+		assert_eq!(Kitties::kitty_price(0), None);
+
+		assert_noop!(Kitties::bid(Origin::signed(2), 0, 99), Error::<Test>::BidTooLow);
+		assert_noop!(Kitties::bid(Origin::signed(1), 0, 150), Error::<Test>::CantBidOwnAuction);
+		assert_ok!(Kitties::bid(Origin::signed(2), 0, 150));
+		assert_eq!(Balances::reserved_balance(2), 150);
+		assert_ok!(Kitties::bid(Origin::signed(3), 0, 200));
+		// The outbid bidder is refunded right away
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(3), 200);
+		assert_noop!(Kitties::bid(Origin::signed(2), 0, 200), Error::<Test>::BidTooLow);
+
+		// The kitty is locked while the auction runs
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyInAuction);
+		assert_noop!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 10))), Error::<Test>::KittyInAuction);
+		assert_noop!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::KittyInAuction);
+		assert_noop!(Kitties::cancel_auction(Origin::signed(1), 0), Error::<Test>::AuctionHasBids);
+
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::AuctionSettled(1, 3, 0, 200))
+		);
+		assert_eq!(Kitties::auction(0), None);
+		assert_eq!(Kitties::kitty_owner(0), Some(3));
+		assert_eq!(owned_kitties(3), vec![0]);
+		assert_eq!(owned_kitties(1), vec![1]);
+		assert_eq!(Balances::reserved_balance(3), 1_000_000);
+		assert_eq!(Balances::free_balance(3), 1_000_000_000 - 200);
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 2 * 1_000_000 + 200);
+		assert_noop!(Kitties::bid(Origin::signed(2), 0, 300), Error::<Test>::AuctionNotExist);
+	})
+}
+
+#[test]
+fn auction_without_bids_keeps_kitty() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(
+			Kitties::start_auction(Origin::signed(1), 0, 100, 10),
+			Error::<Test>::InvalidAuctionEnd
+		);
+		assert_noop!(
+			Kitties::start_auction(Origin::signed(2), 0, 100, 20),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+
+		run_to_block(21);
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::AuctionEndedWithoutWinner(0))
+		);
+		assert_eq!(Kitties::kitty_owner(0), Some(1));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+	})
+}
+
+#[test]
+fn cancel_auction_unlocks_kitty() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+		assert_noop!(Kitties::cancel_auction(Origin::signed(2), 0), Error::<Test>::NotAuctionSeller);
+		assert_ok!(Kitties::cancel_auction(Origin::signed(1), 0));
+
+		assert_eq!(Kitties::auction(0), None);
+		assert!(Kitties::auctions_end_at(20).is_empty());
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+	})
+}
+
+#[test]
+fn auctions_ending_in_a_block_are_capped() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		for _ in 0..3 {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 1, 100, 20));
+		assert_noop!(
+			Kitties::start_auction(Origin::signed(1), 2, 100, 20),
+			Error::<Test>::TooManyAuctions
+		);
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 2, 100, 21));
+
+		// Cancelled auctions free their slot
+		assert_ok!(Kitties::cancel_auction(Origin::signed(1), 0));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+	})
+}
+
+#[test]
+fn dutch_auction_price_decays_to_floor() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(
+			Kitties::start_dutch_auction(Origin::signed(1), 0, 100, 200, 10),
+			Error::<Test>::InvalidDutchAuction
+		);
+		assert_ok!(Kitties::start_dutch_auction(Origin::signed(1), 0, 1000, 200, 10));
+		assert_eq!(Kitties::dutch_auction_price(0), Some(1000));
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyInAuction);
+
+		run_to_block(15);
+		assert_eq!(Kitties::dutch_auction_price(0), Some(600));
+
+		run_to_block(30);
+		assert_eq!(Kitties::dutch_auction_price(0), Some(200));
+	})
+}
+
+#[test]
+fn buy_dutch_at_current_price() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::start_dutch_auction(Origin::signed(1), 0, 1000, 200, 10));
+
+		run_to_block(15);
+		assert_noop!(Kitties::buy_dutch(Origin::signed(2), 0, 599), Error::<Test>::PriceTooLow);
+		assert_noop!(Kitties::buy_dutch(Origin::signed(1), 0, 600), Error::<Test>::CantBuyOwnKitty);
+		assert_ok!(Kitties::buy_dutch(Origin::signed(2), 0, 600));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::KittySold(1, 2, 0, NATIVE, 600))
+		);
+		assert_eq!(Kitties::dutch_auction(0), None);
+		assert_eq!(Kitties::kitty_owner(0), Some(2));
+		assert_eq!(owned_kitties(2), vec![0]);
+		assert_eq!(Balances::reserved_balance(2), 1_000_000);
+		assert_eq!(Balances::free_balance(2), 1_000_000_000 - 600);
+		assert_noop!(Kitties::buy_dutch(Origin::signed(3), 0, 600), Error::<Test>::AuctionNotExist);
+	})
+}
+
+#[test]
+fn cancel_dutch_auction_unlocks_kitty() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::start_dutch_auction(Origin::signed(1), 0, 1000, 200, 10));
+		assert_noop!(Kitties::cancel_dutch_auction(Origin::signed(2), 0), Error::<Test>::NotAuctionSeller);
+		assert_ok!(Kitties::cancel_dutch_auction(Origin::signed(1), 0));
+
+		assert_eq!(Kitties::dutch_auction_price(0), None);
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+	})
+}
+
+#[test]
+fn breed_sets_generation_and_cooldown() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+
+		let child = Kitties::kitties(3).unwrap();
+		assert_eq!(child.generation, 1);
+		assert_eq!(child.birth, 10);
+		assert_eq!(child.cooldown_end, 10);
+		assert_eq!(Kitties::kitties(0).unwrap().cooldown_end, 15);
+		assert_eq!(Kitties::kitties(1).unwrap().cooldown_end, 15);
+
+		assert_err!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::KittyCoolingDown);
+
+		// A newborn can breed right away, a higher generation rests longer
+		set_sex(3, Sex::Male);
+		set_sex(2, Sex::Female);
+		assert_ok!(Kitties::breed(Origin::signed(1), 3, 2));
+		assert_eq!(Kitties::kitties(4).unwrap().generation, 2);
+		assert_eq!(Kitties::kitties(3).unwrap().cooldown_end, 20);
+		assert_eq!(Kitties::kitties(2).unwrap().cooldown_end, 15);
+
+		run_to_block(15);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+	})
+}
+
+#[test]
+fn migrate_to_v1_converts_kitties() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{StorageMap, StorageValue, traits::OnRuntimeUpgrade};
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+		// Legacy layout: bare DNA, a bred kitty and the leftover `Test` value
+		for id in 0u32..4 {
+			sp_io::storage::set(&crate::Kitties::<Test>::hashed_key_for(id), &[id as u8; 16].encode());
+		}
+		crate::KittiesCount::<Test>::put(4);
+		crate::KittyParents::<Test>::insert(2, (0, 1));
+		crate::KittyParents::<Test>::insert(3, (0, 2));
+		put_storage_value(b"Kitties", b"Test", &[], 7u32);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(Kitties::kitties(0).unwrap().generation, 0);
+		assert_eq!(Kitties::kitties(2).unwrap().generation, 1);
+		let grandchild = Kitties::kitties(3).unwrap();
+		assert_eq!(grandchild.dna, [3u8; 16]);
+		assert_eq!(grandchild.generation, 2);
+		assert_eq!(grandchild.cooldown_end, 0);
+		assert_eq!(get_storage_value::<u32>(b"Kitties", b"Test", &[]), None);
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+
+		// Running it again is a no-op
+		assert_eq!(Kitties::on_runtime_upgrade(), 0);
+	})
+}
+
+#[test]
+fn breed_requires_opposite_sex() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Female);
+		set_sex(1, Sex::Female);
+
+		assert_err!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::RequireOppositeSex);
+		assert_eq!(Kitties::kitty_info(0).unwrap().sex, Sex::Female);
+	})
+}
+
+#[test]
+fn breed_rejects_related_kitties() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		set_sex(2, Sex::Female);
+
+		// 3 and 4 are siblings, 5 is their half-sibling through 0
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+		run_to_block(15);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 0));
+		run_to_block(20);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 2));
+		set_sex(3, Sex::Male);
+		set_sex(4, Sex::Female);
+		set_sex(5, Sex::Female);
+
+		assert_err!(Kitties::breed(Origin::signed(1), 3, 4), Error::<Test>::CantBreedSiblings);
+		assert_err!(Kitties::breed(Origin::signed(1), 3, 5), Error::<Test>::CantBreedHalfSiblings);
+		assert_err!(Kitties::breed(Origin::signed(1), 3, 1), Error::<Test>::CantBreedParentAndChild);
+		assert_err!(Kitties::breed(Origin::signed(1), 2, 5), Error::<Test>::RequireOppositeSex);
+		set_sex(5, Sex::Male);
+		assert_err!(Kitties::breed(Origin::signed(1), 2, 5), Error::<Test>::CantBreedParentAndChild);
+
+		// Unrelated kitties are fine
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(6, Sex::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 6, 4));
+	})
+}
+
+#[test]
+fn breed_rejects_parents_with_full_family_lists() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+
+		let full = vec![100u32; crate::FAMILY_WEIGHT_BOUND as usize];
+		crate::FamilyMap::<Test>::insert(0, Relation::Children, &full);
+		assert_noop!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::TooManyRelatives);
+		crate::FamilyMap::<Test>::remove(0, Relation::Children);
+
+		// A full partner list only blocks new partners
+		crate::FamilyMap::<Test>::insert(1, Relation::Partner, &full);
+		assert_noop!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::TooManyRelatives);
+		let mut partners = full.clone();
+		partners[0] = 0;
+		crate::FamilyMap::<Test>::insert(1, Relation::Partner, &partners);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+	})
+}
+
+#[test]
+fn breed_requires_owning_both_parents() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		set_sex(0, Sex::Female);
+		set_sex(1, Sex::Male);
+
+		assert_noop!(Kitties::breed(Origin::signed(1), 0, 1), Error::<Test>::NotKittyOwner);
+		assert_noop!(Kitties::breed(Origin::signed(2), 0, 1), Error::<Test>::NotKittyOwner);
+	})
+}
+
+#[test]
+fn breed_with_sire_pays_fee_to_sire_owner() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		set_sex(0, Sex::Female);
+		set_sex(1, Sex::Male);
+
+		assert_noop!(Kitties::breed_with_sire(Origin::signed(1), 0, 1, 300), Error::<Test>::NotOfferedAsSire);
+		assert_noop!(Kitties::offer_sire(Origin::signed(1), 1, Some(300)), Error::<Test>::NotKittyOwner);
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 1, Some(300)));
+		assert_noop!(Kitties::breed_with_sire(Origin::signed(1), 0, 1, 299), Error::<Test>::PriceTooLow);
+		assert_noop!(Kitties::breed_with_sire(Origin::signed(3), 0, 1, 300), Error::<Test>::NotKittyOwner);
+
+		assert_ok!(Kitties::breed_with_sire(Origin::signed(1), 0, 1, 300));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Created(1, 2))
+		);
+		assert_eq!(Kitties::kitty_owner(2), Some(1));
+		assert_eq!(Kitties::kitty_owner(1), Some(2));
+		assert_eq!(Kitties::parents_of(2), Some((0, 1)));
+		assert_eq!(Balances::free_balance(2), 1_000_000_000 - 1_000_000 + 300);
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 2 * 1_000_000 - 300);
+	})
+}
+
+#[test]
+fn breed_with_own_sire_pays_no_fee() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Female);
+		set_sex(1, Sex::Male);
+		assert_ok!(Kitties::offer_sire(Origin::signed(1), 1, Some(300)));
+
+		assert_ok!(Kitties::breed_with_sire(Origin::signed(1), 0, 1, 300));
+
+		assert!(System::events().iter().all(|record| {
+			record.event != TestEvent::kitty_event(Event::<Test>::SireFeePaid(1, 1, 1, 300))
+		}));
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 3 * 1_000_000);
+	})
+}
+
+#[test]
+fn sire_offer_is_withdrawn_and_cleared_on_transfer() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		set_sex(0, Sex::Female);
+		set_sex(1, Sex::Male);
+
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 1, Some(300)));
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 1, None));
+		assert_eq!(Kitties::sire_fee(1), None);
+
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 1, Some(300)));
+		assert_ok!(Kitties::transfer(Origin::signed(2), 3, 1));
+		assert_eq!(Kitties::sire_fee(1), None);
+		assert_noop!(Kitties::breed_with_sire(Origin::signed(1), 0, 1, 300), Error::<Test>::NotOfferedAsSire);
+	})
+}
+
+#[test]
+fn release_kitty_returns_stake() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_eq!(Balances::reserved_balance(1), 2_000_000);
+
+		assert_noop!(Kitties::release(Origin::signed(2), 0), Error::<Test>::NotKittyOwner);
+		assert_ok!(Kitties::release(Origin::signed(1), 0));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Released(1, 0))
+		);
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+		assert_eq!(Kitties::kitties(0), None);
+		assert_eq!(Kitties::kitty_owner(0), None);
+		assert_eq!(Kitties::kitty_price(0), None);
+		assert_eq!(owned_kitties(1), vec![1]);
+		assert_noop!(Kitties::release(Origin::signed(1), 0), Error::<Test>::InvalidKittyId);
+	})
+}
+
+#[test]
+fn release_kitty_failed_in_auction() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 20));
+
+		assert_noop!(Kitties::release(Origin::signed(1), 0), Error::<Test>::KittyInAuction);
+	})
+}
+
+#[test]
+fn release_kitty_keeps_family_tree_consistent() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+		run_to_block(15);
+		assert_ok!(Kitties::breed(Origin::signed(1), 0, 1));
+		set_sex(2, Sex::Male);
+		set_sex(3, Sex::Female);
+
+		assert_ok!(Kitties::release(Origin::signed(1), 3));
+		assert_eq!(Kitties::family_map(0, Relation::Children), vec![2]);
+		assert_eq!(Kitties::family_map(1, Relation::Children), vec![2]);
+		assert_eq!(Kitties::sibling((0, 1)), vec![2]);
+		assert_eq!(Kitties::parents_of(3), None);
+
+		assert_ok!(Kitties::release(Origin::signed(1), 0));
+		assert_eq!(Kitties::family_map(1, Relation::Partner), Vec::<u32>::new());
+		assert_eq!(Kitties::family_map(0, Relation::Children), Vec::<u32>::new());
+		// The child's lineage is kept
+		assert_eq!(Kitties::parents_of(2), Some((0, 1)));
+		assert_err!(Kitties::breed(Origin::signed(1), 2, 1), Error::<Test>::CantBreedParentAndChild);
+	})
+}
+
+#[test]
+fn migrate_to_v2_rekeys_family_map() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{Blake2_128Concat, StorageHasher, StorageValue, IterableStorageDoubleMap, traits::OnRuntimeUpgrade};
+		use frame_support::storage::migration::put_storage_value;
+
+		let legacy_key = |kitty_id: u32, relation: &str| {
+			let mut key = Blake2_128Concat::hash(&kitty_id.encode());
+			key.extend(Blake2_128Concat::hash(&relation.encode()));
+			key
+		};
+		crate::StorageVersion::put(crate::Releases::V1);
+		put_storage_value(b"Kitties", b"FamilyMap", &legacy_key(0, "Children"), vec![2u32, 3]);
+		put_storage_value(b"Kitties", b"FamilyMap", &legacy_key(0, "Partner"), vec![1u32]);
+		put_storage_value(b"Kitties", b"FamilyMap", &legacy_key(1, "Partner"), vec![0u32]);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(Kitties::family_map(0, Relation::Children), vec![2, 3]);
+		assert_eq!(Kitties::family_map(0, Relation::Partner), vec![1]);
+		assert_eq!(Kitties::family_map(1, Relation::Partner), vec![0]);
+		assert_eq!(Kitties::family_map(1, Relation::Children), Vec::<u32>::new());
+		assert_eq!(crate::FamilyMap::<Test>::iter_prefix(0).count(), 2);
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+	})
+}
+
+#[test]
+fn decode_dna_follows_gene_layout() {
+	use crate::genes::{decode_dna, Colour, EyeShape, Pattern, Rarity};
+
+	let mut dna = [0u8; 16];
+	dna[0] = 3;
+	dna[1] = 11;
+	dna[2] = 2;
+	dna[3] = 7;
+	dna[4] = 5;
+	dna[5] = 8;
+	dna[6] = 250;
+
+	let phenotype = decode_dna(&dna);
+	assert_eq!(phenotype.sex, Sex::Female);
+	assert_eq!(phenotype.body_colour, Colour::Ginger);
+	assert_eq!(phenotype.eye_shape, EyeShape::Sleepy);
+	assert_eq!(phenotype.eye_colour, Colour::Lilac);
+	assert_eq!(phenotype.pattern, Pattern::Tabby);
+	assert_eq!(phenotype.pattern_colour, Colour::Black);
+	assert_eq!(phenotype.rarity, Rarity::Rare);
+
+	dna[6] = 100;
+	assert_eq!(decode_dna(&dna).rarity, Rarity::Common);
+	dna[6] = 255;
+	assert_eq!(decode_dna(&dna).rarity, Rarity::Legendary);
+}
+
+#[test]
+fn mutate_respects_chance() {
+	use crate::genes::mutate;
+	use sp_runtime::Permill;
+
+	let mut entropy = [0u8; 16];
+	// roll = 500_000, gene 6, new value 255
+	entropy[..4].copy_from_slice(&500_000u32.to_le_bytes());
+	entropy[4] = 22;
+	entropy[5] = 255;
+
+	let mut dna = [1u8; 16];
+	assert!(!mutate(&mut dna, &entropy, Permill::from_percent(50)));
+	assert_eq!(dna, [1u8; 16]);
+
+	assert!(mutate(&mut dna, &entropy, Permill::from_percent(51)));
+	let mut expected = [1u8; 16];
+	expected[6] = 255;
+	assert_eq!(dna, expected);
+}
+
+#[test]
+fn phenotype_of_kitty() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+
+		assert_eq!(Kitties::phenotype(0), Some(crate::decode_dna(&Kitties::kitties(0).unwrap().dna)));
+		assert_eq!(Kitties::phenotype(0).unwrap().sex, Sex::Male);
+		assert_eq!(Kitties::phenotype(1), None);
+	})
+}
+
+#[test]
+fn transfer_batch_moves_all_kitties() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		for _ in 0..4 {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+		assert_ok!(Kitties::ask(Origin::signed(1), 2, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::transfer_batch(Origin::signed(1), 2, vec![0, 2, 3]));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Transfered(1, 2, 3))
+		);
+		assert_eq!(owned_kitties(1), vec![1]);
+		assert_eq!(owned_kitties(2), vec![0, 2, 3]);
+		assert_eq!(Kitties::kitty_owner(2), Some(2));
+		assert_eq!(Kitties::kitty_price(2), None);
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+		assert_eq!(Balances::reserved_balance(2), 3_000_000);
+	})
+}
+
+#[test]
+fn transfer_batch_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(3)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_noop!(
+			Kitties::transfer_batch(Origin::signed(1), 2, vec![0, 1, 2]),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_noop!(
+			Kitties::transfer_batch(Origin::signed(1), 2, vec![0, 2, 0]),
+			Error::<Test>::DuplicateKitty
+		);
+		assert_noop!(
+			Kitties::transfer_batch(Origin::signed(1), 2, vec![0; crate::MAX_BATCH_SIZE as usize + 1]),
+			Error::<Test>::BatchTooLarge
+		);
+		assert_eq!(owned_kitties(1), vec![0, 2]);
+	})
+}
+
+#[test]
+fn breed_batch_breeds_all_pairs() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		for _ in 0..4 {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		set_sex(2, Sex::Male);
+		set_sex(3, Sex::Female);
+
+		assert_noop!(
+			Kitties::breed_batch(Origin::signed(1), vec![(0, 1), (2, 2)]),
+			Error::<Test>::RequireDifferentParent
+		);
+		assert_noop!(
+			Kitties::breed_batch(Origin::signed(1), vec![(0, 1); crate::MAX_BATCH_SIZE as usize + 1]),
+			Error::<Test>::BatchTooLarge
+		);
+
+		assert_ok!(Kitties::breed_batch(Origin::signed(1), vec![(0, 1), (2, 3)]));
+		assert_eq!(owned_kitties(1), vec![0, 1, 2, 3, 4, 5]);
+		assert_eq!(Kitties::parents_of(4), Some((0, 1)));
+		assert_eq!(Kitties::parents_of(5), Some((2, 3)));
+		assert_eq!(Balances::reserved_balance(1), 6_000_000);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Created(1, 5))
+		);
+	})
+}
+
+#[test]
+fn transfer_from_with_approval() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(Kitties::transfer_from(Origin::signed(2), 1, 3, 0), Error::<Test>::NotApproved);
+		assert_noop!(Kitties::approve(Origin::signed(2), Some(2), 0), Error::<Test>::NotApproved);
+		assert_noop!(Kitties::approve(Origin::signed(1), Some(1), 0), Error::<Test>::CantApproveSelf);
+
+		assert_ok!(Kitties::approve(Origin::signed(1), Some(2), 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Approval(1, Some(2), 0))
+		);
+		assert_eq!(Kitties::approved(0), Some(2));
+
+		assert_ok!(Kitties::transfer_from(Origin::signed(2), 1, 3, 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Transfered(1, 3, 0))
+		);
+		assert_eq!(Kitties::kitty_owner(0), Some(3));
+		assert_eq!(Kitties::approved(0), None);
+		assert_eq!(Balances::reserved_balance(3), 1_000_000);
+
+		// The approval went away with the transfer
+		assert_noop!(Kitties::transfer_from(Origin::signed(2), 3, 2, 0), Error::<Test>::NotApproved);
+	})
+}
+
+#[test]
+fn approval_can_be_revoked() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::approve(Origin::signed(1), Some(2), 0));
+		assert_ok!(Kitties::approve(Origin::signed(1), None, 0));
+
+		assert_eq!(Kitties::approved(0), None);
+		assert_noop!(Kitties::transfer_from(Origin::signed(2), 1, 2, 0), Error::<Test>::NotApproved);
+	})
+}
+
+#[test]
+fn operator_can_approve_and_transfer_all_kitties() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(
+			Kitties::set_approval_for_all(Origin::signed(1), 1, true),
+			Error::<Test>::CantApproveSelf
+		);
+		assert_ok!(Kitties::set_approval_for_all(Origin::signed(1), 2, true));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::ApprovalForAll(1, 2, true))
+		);
+
+		assert_ok!(Kitties::transfer_from(Origin::signed(2), 1, 2, 0));
+		assert_ok!(Kitties::approve(Origin::signed(2), Some(3), 1));
+		assert_ok!(Kitties::transfer_from(Origin::signed(3), 1, 3, 1));
+		assert_eq!(owned_kitties(2), vec![0]);
+		assert_eq!(owned_kitties(3), vec![1]);
+
+		// The operator only manages the kitties of the account that approved it
+		assert_noop!(Kitties::transfer_from(Origin::signed(2), 3, 2, 1), Error::<Test>::NotApproved);
+
+		assert_ok!(Kitties::set_approval_for_all(Origin::signed(1), 2, false));
+		assert!(!Kitties::is_approved_for_all(1, 2));
+	})
+}
+
+#[test]
+fn approvals_are_cleared_on_sale_and_release() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::approve(Origin::signed(1), Some(3), 0));
+		assert_ok!(Kitties::approve(Origin::signed(1), Some(3), 1));
+
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+		assert_ok!(Kitties::buy(Origin::signed(2), 0, NATIVE, 1000));
+		assert_eq!(Kitties::approved(0), None);
+
+		assert_ok!(Kitties::release(Origin::signed(1), 1));
+		assert_eq!(Kitties::approved(1), None);
+	})
+}
+
+#[test]
+fn set_metadata_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(
+			Kitties::set_metadata(Origin::signed(2), 0, b"Tom".to_vec(), vec![]),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_noop!(
+			Kitties::set_metadata(Origin::signed(1), 0, b"Tom the cat".to_vec(), vec![]),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			Kitties::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), vec![0; 33]),
+			Error::<Test>::DescriptionTooLong
+		);
+
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), b"A cat".to_vec()));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::MetadataSet(1, 0, 80))
+		);
+		assert_eq!(Balances::reserved_balance(1), 1_000_080);
+		assert_eq!(
+			Kitties::kitty_info(0).unwrap().metadata,
+			Some(crate::KittyMetadata { name: b"Tom".to_vec(), description: b"A cat".to_vec() })
+		);
+
+		// Replacing the metadata only keeps the new deposit
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 0, b"Tommy".to_vec(), vec![]));
+		assert_eq!(Balances::reserved_balance(1), 1_000_050);
+	})
+}
+
+#[test]
+fn clear_metadata_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(Kitties::clear_metadata(Origin::signed(1), 0), Error::<Test>::MetadataNotExist);
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), vec![]));
+
+		assert_ok!(Kitties::clear_metadata(Origin::signed(1), 0));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::MetadataCleared(1, 0))
+		);
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+		assert_eq!(Kitties::metadata(0), None);
+		assert_eq!(Kitties::kitty_info(0).unwrap().metadata, None);
+	})
+}
+
+#[test]
+fn metadata_deposit_follows_the_depositor() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), vec![]));
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 1, b"Tim".to_vec(), vec![]));
+
+		// The metadata moves with the kitty, the deposit stays with whoever paid it
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(Kitties::metadata(0).unwrap().metadata.name, b"Tom".to_vec());
+		assert_eq!(Balances::reserved_balance(1), 1_000_060);
+
+		assert_ok!(Kitties::set_metadata(Origin::signed(2), 0, b"Jerry".to_vec(), vec![]));
+		assert_eq!(Balances::reserved_balance(1), 1_000_030);
+		assert_eq!(Balances::reserved_balance(2), 1_000_050);
+
+		// Releasing a kitty refunds its metadata deposit
+		assert_ok!(Kitties::release(Origin::signed(1), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Kitties::metadata(1), None);
+	})
+}
+
+#[test]
+fn buy_kitty_in_other_currency() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((DOT, 1000))));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::KittyPriceSet(1, 0, Some((DOT, 1000))))
+		);
+
+		assert_noop!(Kitties::buy(Origin::signed(2), 0, NATIVE, 1000), Error::<Test>::WrongCurrency);
+		assert_ok!(Kitties::buy(Origin::signed(2), 0, DOT, 1000));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::KittySold(1, 2, 0, DOT, 1000))
+		);
+		assert_eq!(Kitties::kitty_owner(0), Some(2));
+		assert_eq!(Tokens::free_balance(DOT, &1), 1_001_000);
+		assert_eq!(Tokens::free_balance(DOT, &2), 999_000);
+		assert_eq!(Balances::free_balance(2), 1_000_000_000 - 1_000_000);
+	})
+}
+
+#[test]
+fn migrate_to_v3_prices_listings_in_native_currency() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{Blake2_128Concat, StorageHasher, StorageValue, traits::OnRuntimeUpgrade};
+		use frame_support::storage::migration::put_storage_value;
+
+		crate::StorageVersion::put(crate::Releases::V2);
+		put_storage_value(b"Kitties", b"KittyPrices", &Blake2_128Concat::hash(&0u32.encode()), 1000u64);
+		put_storage_value(b"Kitties", b"KittyPrices", &Blake2_128Concat::hash(&3u32.encode()), 50u64);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(Kitties::kitty_price(0), Some((NATIVE, 1000)));
+		assert_eq!(Kitties::kitty_price(3), Some((NATIVE, 50)));
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+	})
+}
+
+#[test]
+fn create_and_breed_fail_without_stake() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Currency;
+
+		run_to_block(10);
+
+		assert_noop!(Kitties::create(Origin::signed(6)), Error::<Test>::NotEnoughBalance);
+
+		let _ = Balances::deposit_creating(&6, 2_000_100);
+		assert_ok!(Kitties::create(Origin::signed(6)));
+		assert_ok!(Kitties::create(Origin::signed(6)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+
+		assert_noop!(Kitties::breed(Origin::signed(6), 0, 1), Error::<Test>::NotEnoughBalance);
+		assert_eq!(Kitties::kitties_count(), 2);
+	})
+}
+
+#[test]
+fn kitty_deposit_is_recorded_and_moved() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::ReservableCurrency;
+
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::kitty_deposit(0), 1_000_000);
+
+		// A kitty staked before `StakeForKitty` was raised only moves and refunds what it holds
+		crate::KittyDeposits::<Test>::insert(0, 500_000);
+		Balances::unreserve(&1, 500_000);
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 500_000);
+		assert_eq!(Kitties::kitty_deposit(0), 500_000);
+
+		assert_ok!(Kitties::release(Origin::signed(2), 0));
+		assert_eq!(
+			System::events()[System::events().len() - 2].event,
+			TestEvent::kitty_event(Event::<Test>::UnstakeForKitty(2, 500_000))
+		);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Kitties::kitty_deposit(0), 0);
+	})
+}
+
+#[test]
+fn transfer_fails_when_deposit_is_not_reserved() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		crate::KittyDeposits::<Test>::insert(0, 2_000_000);
+
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 0), Error::<Test>::NotEnoughBalance);
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+	})
+}
+
+#[test]
+fn migrate_to_v4_records_deposits() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{StorageValue, StorageMap, traits::OnRuntimeUpgrade};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		crate::KittyDeposits::<Test>::remove(0);
+		crate::KittyDeposits::<Test>::remove(1);
+		crate::StorageVersion::put(crate::Releases::V3);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(Kitties::kitty_deposit(0), 1_000_000);
+		assert_eq!(Kitties::kitty_deposit(1), 1_000_000);
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+	})
+}
+
+#[test]
+fn migrate_to_v4_only_records_reserved_stakes() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{StorageValue, StorageMap, traits::OnRuntimeUpgrade};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		// Sales used to hand kitties over and leave their stake reserved by the seller
+		crate::KittyOwners::<Test>::insert(1, 2);
+		crate::KittyDeposits::<Test>::remove(0);
+		crate::KittyDeposits::<Test>::remove(1);
+		crate::StorageVersion::put(crate::Releases::V3);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(Kitties::kitty_deposit(0), 1_000_000);
+		assert_eq!(Kitties::kitty_deposit(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 2_000_000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// Only the recorded deposits move with the kitties
+		assert_ok!(Kitties::transfer(Origin::signed(2), 3, 1));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 3, 0));
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(3), 1_000_000);
+	})
+}
+
+// 0 and 1 are founders, 2 and 3 their children, 4 a child of the siblings 2 and 3, and 6 a child
+// of 4 and the founder 5. Written directly, as breeding siblings isn't allowed.
+fn inbred_family() {
+	for _ in 0..7 {
+		assert_ok!(Kitties::create(Origin::signed(1)));
+	}
+	for &(child, parents) in [(2, (0, 1)), (3, (0, 1)), (4, (2, 3)), (6, (4, 5))].iter() {
+		crate::KittyParents::<Test>::insert(child, parents);
+	}
+}
+
+#[test]
+fn pedigree_lists_ancestors_breadth_first() {
+	new_test_ext().execute_with(|| {
+		use crate::PedigreeNode;
+
+		run_to_block(10);
+		inbred_family();
+
+		assert_eq!(Kitties::pedigree(6, 1), vec![
+			PedigreeNode { id: 6, depth: 0, parents: Some((4, 5)) },
+			PedigreeNode { id: 4, depth: 1, parents: Some((2, 3)) },
+			PedigreeNode { id: 5, depth: 1, parents: None },
+		]);
+
+		// Shared ancestors are listed once
+		let pedigree = Kitties::pedigree(6, 10);
+		assert_eq!(pedigree.iter().map(|node| node.id).collect::<Vec<_>>(), vec![6, 4, 5, 2, 3, 0, 1]);
+		assert_eq!(pedigree.iter().map(|node| node.depth).collect::<Vec<_>>(), vec![0, 1, 1, 2, 2, 3, 3]);
+		assert_eq!(Kitties::pedigree(7, 3), vec![]);
+	})
+}
+
+#[test]
+fn common_ancestor_is_the_nearest_one() {
+	new_test_ext().execute_with(|| {
+		use crate::CommonAncestor;
+
+		run_to_block(10);
+		inbred_family();
+
+		assert_eq!(
+			Kitties::common_ancestor(2, 3),
+			Some(CommonAncestor { id: 0, distance_1: 1, distance_2: 1 })
+		);
+		assert_eq!(
+			Kitties::common_ancestor(6, 4),
+			Some(CommonAncestor { id: 4, distance_1: 1, distance_2: 0 })
+		);
+		assert_eq!(
+			Kitties::common_ancestor(3, 6),
+			Some(CommonAncestor { id: 3, distance_1: 0, distance_2: 2 })
+		);
+		assert_eq!(Kitties::common_ancestor(5, 2), None);
+		assert_eq!(Kitties::common_ancestor(7, 2), None);
+	})
+}
+
+#[test]
+fn inbreeding_coefficient_follows_wright() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::Perbill;
+
+		run_to_block(10);
+		inbred_family();
+
+		assert_eq!(Kitties::inbreeding_coefficient(0), Some(Perbill::zero()));
+		assert_eq!(Kitties::inbreeding_coefficient(2), Some(Perbill::zero()));
+		// Child of full siblings
+		assert_eq!(Kitties::inbreeding_coefficient(4), Some(Perbill::from_percent(25)));
+		assert_eq!(Kitties::inbreeding_coefficient(6), Some(Perbill::zero()));
+		assert_eq!(Kitties::inbreeding_coefficient(7), None);
+
+		assert_eq!(Kitties::pairing_inbreeding_coefficient(2, 3), Some(Perbill::from_percent(25)));
+		// Parent and its inbred child
+		assert_eq!(Kitties::pairing_inbreeding_coefficient(4, 2), Some(Perbill::from_rational_approximation(3u32, 8)));
+		assert_eq!(Kitties::pairing_inbreeding_coefficient(5, 1), Some(Perbill::zero()));
+	})
+}
+
+#[test]
+fn rental_lends_a_kitty_until_it_expires() {
+	new_test_ext().execute_with(|| {
+		use crate::{Rental, RentalOffer};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		set_sex(2, Sex::Female);
+
+		assert_noop!(Kitties::rent(Origin::signed(2), 0, 500), Error::<Test>::NotOfferedForRent);
+		assert_noop!(
+			Kitties::offer_rental(Origin::signed(2), 0, Some(RentalOffer { price: 500, duration: 5 })),
+			Error::<Test>::NotKittyOwner
+		);
+		assert_noop!(
+			Kitties::offer_rental(Origin::signed(1), 0, Some(RentalOffer { price: 500, duration: 0 })),
+			Error::<Test>::InvalidRentalDuration
+		);
+		assert_ok!(Kitties::offer_rental(Origin::signed(1), 0, Some(RentalOffer { price: 500, duration: 5 })));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))));
+
+		assert_noop!(Kitties::rent(Origin::signed(1), 0, 500), Error::<Test>::CantBuyOwnKitty);
+		assert_noop!(Kitties::rent(Origin::signed(2), 0, 499), Error::<Test>::PriceTooLow);
+		assert_ok!(Kitties::rent(Origin::signed(2), 0, 500));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::Rented(1, 2, 0, 500, 15))
+		);
+		assert_noop!(Kitties::rent(Origin::signed(3), 0, 500), Error::<Test>::KittyRented);
+
+		// Renting paid the owner and cancelled the listing
+		assert_eq!(Balances::free_balance(1), 1_000_000_000 - 2 * 1_000_000 + 500);
+		assert_eq!(Kitties::kitty_price(0), None);
+		assert_eq!(Kitties::kitty_owner(0), Some(1));
+		assert_eq!(Kitties::kitty_user(0), Some(2));
+		let info = Kitties::kitty_info(0).unwrap();
+		assert_eq!((info.owner, info.user), (1, 2));
+		assert_eq!(info.rental, Some(Rental { renter: 2, end: 15 }));
+
+		// The owner can't dispose of or use the kitty
+		assert_noop!(Kitties::transfer(Origin::signed(1), 3, 0), Error::<Test>::KittyRented);
+		assert_noop!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 1000))), Error::<Test>::KittyRented);
+		assert_noop!(Kitties::release(Origin::signed(1), 0), Error::<Test>::KittyRented);
+		assert_noop!(Kitties::breed(Origin::signed(1), 0, 2), Error::<Test>::KittyRented);
+
+		// The renter can use it, but not transfer it
+		assert_noop!(Kitties::transfer(Origin::signed(2), 3, 0), Error::<Test>::NotKittyOwner);
+		assert_ok!(Kitties::breed(Origin::signed(2), 0, 1));
+		assert_eq!(Kitties::kitty_owner(3), Some(2));
+
+		run_to_block(14);
+		assert_eq!(Kitties::kitty_user(0), Some(2));
+
+		run_to_block(15);
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::RentalEnded(2, 0))
+		);
+		assert_eq!(Kitties::rental(0), None);
+		assert_eq!(Kitties::kitty_user(0), Some(1));
+		assert_eq!(Kitties::kitty_info(0).unwrap().user, 1);
+
+		// The offer stays for the next renter until withdrawn or the kitty changes hands
+		assert_eq!(Kitties::rental_offer(0), Some(RentalOffer { price: 500, duration: 5 }));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 3, 0));
+		assert_eq!(Kitties::rental_offer(0), None);
+	})
+}
+
+#[test]
+fn rental_offer_can_be_withdrawn() {
+	new_test_ext().execute_with(|| {
+		use crate::RentalOffer;
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::offer_rental(Origin::signed(1), 0, Some(RentalOffer { price: 500, duration: 5 })));
+		assert_ok!(Kitties::offer_rental(Origin::signed(1), 0, None));
+
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::RentalOffered(1, 0, None))
+		);
+		assert_noop!(Kitties::rent(Origin::signed(2), 0, 500), Error::<Test>::NotOfferedForRent);
+	})
+}
+
+#[test]
+fn rentals_ending_in_a_block_are_capped() {
+	new_test_ext().execute_with(|| {
+		use crate::RentalOffer;
+
+		run_to_block(10);
+		for kitty_id in 0..3 {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+			assert_ok!(Kitties::offer_rental(Origin::signed(1), kitty_id, Some(RentalOffer { price: 500, duration: 5 })));
+		}
+		assert_ok!(Kitties::rent(Origin::signed(2), 0, 500));
+		assert_ok!(Kitties::rent(Origin::signed(2), 1, 500));
+		assert_noop!(Kitties::rent(Origin::signed(2), 2, 500), Error::<Test>::TooManyRentals);
+
+		run_to_block(11);
+		assert_ok!(Kitties::rent(Origin::signed(2), 2, 500));
+		assert_eq!(Kitties::rentals_end_at(16), vec![2]);
+	})
+}
+
+#[test]
+fn genesis_mints_kitties() {
+	let mut dna = [7u8; 16];
+	dna[0] = 1;
+	new_test_ext_with_kitties(vec![(1, [0u8; 16], None), (2, dna, Some((DOT, 500))), (1, [2u8; 16], None)]).execute_with(|| {
+		assert_eq!(Kitties::kitties_count(), 3);
+		assert_eq!(owned_kitties(1), vec![0, 2]);
+		assert_eq!(owned_kitties(2), vec![1]);
+
+		let info = Kitties::kitty_info(1).unwrap();
+		assert_eq!(info.dna, dna);
+		assert_eq!(info.sex, Sex::Female);
+		assert_eq!(info.generation, 0);
+		assert_eq!(info.price, Some((DOT, 500)));
+		assert_eq!(Kitties::kitty_price(0), None);
+
+		// The stake is reserved like for created kitties
+		assert_eq!(Balances::reserved_balance(1), 2_000_000);
+		assert_eq!(Kitties::kitty_deposit(1), 1_000_000);
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+
+		assert_ok!(Kitties::buy(Origin::signed(1), 1, DOT, 500));
+		assert_eq!(Kitties::kitty_owner(1), Some(1));
+	})
+}
+
+#[test]
+fn kitties_created_in_one_block_get_different_dna() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_ne!(Kitties::kitties(0).unwrap().dna, Kitties::kitties(1).unwrap().dna);
+	})
+}
+
+#[test]
+fn reveal_create_needs_an_old_enough_commitment() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		run_to_block(10);
+		let secret = [42u8; 32];
+		let commitment = BlakeTwo256::hash_of(&secret);
+
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), secret), Error::<Test>::CommitmentNotExist);
+		assert_ok!(Kitties::commit_dna(Origin::signed(1), commitment));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::DnaCommitted(1, commitment))
+		);
+		assert_noop!(Kitties::commit_dna(Origin::signed(1), commitment), Error::<Test>::CommitmentExists);
+		assert_eq!(Kitties::dna_commitment(1, commitment), Some(DnaCommitment { reveal_block: 13, deposit: 100 }));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		// The hash of the reveal block is only known once it is finished
+		run_to_block(13);
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), secret), Error::<Test>::RevealTooEarly);
+		// Commitments are per account
+		assert_noop!(Kitties::reveal_create(Origin::signed(2), secret), Error::<Test>::CommitmentNotExist);
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), [0u8; 32]), Error::<Test>::CommitmentNotExist);
+
+		run_to_block(14);
+		assert_ok!(Kitties::reveal_create(Origin::signed(1), secret));
+		assert_eq!(Kitties::kitty_owner(0), Some(1));
+		assert_eq!(Kitties::kitty_deposit(0), 1_000_000);
+		assert_eq!(Kitties::dna_commitment(1, commitment), None);
+		assert_eq!(Balances::reserved_balance(1), 1_000_000);
+
+		// A commitment is used once
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), secret), Error::<Test>::CommitmentNotExist);
+	})
+}
+
+#[test]
+fn reveal_breed_works() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_sex(0, Sex::Male);
+		set_sex(1, Sex::Female);
+		let secret = [7u8; 32];
+		assert_ok!(Kitties::commit_dna(Origin::signed(1), BlakeTwo256::hash_of(&secret)));
+
+		run_to_block(14);
+		// The commitment is kept when breeding fails
+		assert_noop!(Kitties::reveal_breed(Origin::signed(2), 0, 1, secret), Error::<Test>::CommitmentNotExist);
+		assert_noop!(Kitties::reveal_breed(Origin::signed(1), 0, 0, secret), Error::<Test>::RequireDifferentParent);
+		assert_ok!(Kitties::reveal_breed(Origin::signed(1), 0, 1, secret));
+
+		assert_eq!(Kitties::kitty_owner(2), Some(1));
+		assert_eq!(Kitties::parents_of(2), Some((0, 1)));
+		assert_eq!(owned_kitties(1), vec![0, 1, 2]);
+	})
+}
+
+#[test]
+fn owned_kitties_are_counted() {
+	new_test_ext().execute_with(|| {
+		use frame_support::StorageMap;
+
+		run_to_block(10);
+		for _ in 0..3 {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+		assert_eq!(Kitties::owned_kitties_count(1), 3);
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 1));
+		assert_ok!(Kitties::transfer_batch(Origin::signed(1), 2, vec![0, 2]));
+		assert_eq!(Kitties::owned_kitties_count(2), 3);
+		assert_eq!(owned_kitties(2), vec![0, 1, 2]);
+
+		// Accounts without kitties leave nothing behind
+		assert_eq!(Kitties::owned_kitties_count(1), 0);
+		assert!(!crate::OwnedKittiesCount::<Test>::contains_key(1));
+		assert_eq!(Kitties::owned_kitties(&1).next(), None);
+
+		assert_ok!(Kitties::release(Origin::signed(2), 1));
+		assert_eq!(Kitties::owned_kitties_count(2), 2);
+		assert_eq!(owned_kitties(2), vec![0, 2]);
+	})
+}
+
+#[test]
+fn migrate_to_v5_rebuilds_the_ownership_index() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::{Blake2_128Concat, StorageHasher, StorageValue, StorageMap, traits::OnRuntimeUpgrade};
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+		// Legacy layout: a list of kitties per owner, and no counts
+		for &(kitty_id, owner) in [(0u32, 1u64), (1, 1), (2, 2)].iter() {
+			crate::KittyOwners::<Test>::insert(kitty_id, owner);
+		}
+		let key = Blake2_128Concat::hash(&1u64.encode());
+		put_storage_value(b"Kitties", b"AccountKitties", &key, vec![0u32, 1]);
+		crate::StorageVersion::put(crate::Releases::V4);
+
+		Kitties::on_runtime_upgrade();
+
+		assert_eq!(get_storage_value::<Vec<u32>>(b"Kitties", b"AccountKitties", &key), None);
+		assert_eq!(owned_kitties(1), vec![0, 1]);
+		assert_eq!(owned_kitties(2), vec![2]);
+		assert_eq!(Kitties::owned_kitties_count(1), 2);
+		assert_eq!(Kitties::owned_kitties_count(2), 1);
+		assert_eq!(crate::StorageVersion::get(), Some(crate::Releases::V5));
+	})
+}
+
+#[test]
+fn revealed_dna_is_drawn_from_the_reveal_block() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::StorageMap;
+		use sp_core::H256;
+		use sp_io::hashing::blake2_128;
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		run_to_block(10);
+		let secret = [42u8; 32];
+		assert_ok!(Kitties::commit_dna(Origin::signed(1), BlakeTwo256::hash_of(&secret)));
+		frame_system::BlockHash::<Test>::insert(13, H256::repeat_byte(7));
+
+		// Revealing later doesn't change the outcome
+		run_to_block(17);
+		assert_ok!(Kitties::reveal_create(Origin::signed(1), secret));
+		let dna = (secret, H256::repeat_byte(7), 1u64).using_encoded(blake2_128);
+		assert_eq!(Kitties::kitties(0).unwrap().dna, dna);
+	})
+}
+
+#[test]
+fn expired_dna_commitments_are_void() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		run_to_block(10);
+		let secret = [42u8; 32];
+		let commitment = BlakeTwo256::hash_of(&secret);
+		assert_ok!(Kitties::commit_dna(Origin::signed(1), commitment));
+		let free = Balances::free_balance(1);
+
+		// Reveal block 13, revealable up to block 18
+		run_to_block(18);
+		assert_noop!(
+			Kitties::clear_dna_commitment(Origin::signed(2), 1, commitment),
+			Error::<Test>::CommitmentNotExpired
+		);
+
+		run_to_block(19);
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), secret), Error::<Test>::CommitmentExpired);
+		assert_noop!(
+			Kitties::clear_dna_commitment(Origin::signed(2), 1, [0u8; 32].into()),
+			Error::<Test>::CommitmentNotExist
+		);
+		assert_ok!(Kitties::clear_dna_commitment(Origin::signed(2), 1, commitment));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			TestEvent::kitty_event(Event::<Test>::DnaCommitmentCleared(1, commitment))
+		);
+
+		// The deposit is lost
+		assert_eq!(Kitties::dna_commitment(1, commitment), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), free);
+		assert_noop!(Kitties::reveal_create(Origin::signed(1), secret), Error::<Test>::CommitmentNotExist);
+	})
+}
+
+#[test]
+fn commit_dna_needs_the_deposit() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		run_to_block(10);
+		assert_noop!(
+			Kitties::commit_dna(Origin::signed(9), BlakeTwo256::hash_of(&[0u8; 32])),
+			Error::<Test>::NotEnoughBalance
+		);
+	})
+}
+
+#[test]
+fn provenance_records_every_ownership_change() {
+	new_test_ext().execute_with(|| {
+		use crate::{ProvenanceEntry, TransferKind};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::provenance(0), vec![]);
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(Kitties::ask(Origin::signed(2), 0, Some((DOT, 500))));
+		assert_ok!(Kitties::buy(Origin::signed(1), 0, DOT, 500));
+		run_to_block(11);
+		assert_ok!(Kitties::start_dutch_auction(Origin::signed(1), 0, 1000, 1000, 5));
+		assert_ok!(Kitties::buy_dutch(Origin::signed(3), 0, 1000));
+
+		assert_eq!(Kitties::provenance(0), vec![
+			ProvenanceEntry { from: 1, to: 2, block: 10, kind: TransferKind::Gift, price: None },
+			ProvenanceEntry { from: 2, to: 1, block: 10, kind: TransferKind::Sale, price: Some((DOT, 500)) },
+			ProvenanceEntry { from: 1, to: 3, block: 11, kind: TransferKind::Auction, price: Some((NATIVE, 1000)) },
+		]);
+
+		// Only the latest `MaxProvenanceEntries` are kept
+		run_to_block(12);
+		assert_ok!(Kitties::transfer_batch(Origin::signed(3), 4, vec![0]));
+		let history = Kitties::provenance(0);
+		assert_eq!(history.len(), 3);
+		assert_eq!(history[0].kind, TransferKind::Sale);
+		assert_eq!(history[2], ProvenanceEntry { from: 3, to: 4, block: 12, kind: TransferKind::Gift, price: None });
+
+		assert_ok!(Kitties::release(Origin::signed(4), 0));
+		assert_eq!(Kitties::provenance(0), vec![]);
+	})
+}
+
+#[test]
+fn provenance_records_auction_wins() {
+	new_test_ext().execute_with(|| {
+		use crate::{ProvenanceEntry, TransferKind};
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::start_auction(Origin::signed(1), 0, 100, 12));
+		assert_ok!(Kitties::bid(Origin::signed(2), 0, 300));
+		run_to_block(13);
+
+		assert_eq!(Kitties::provenance(0), vec![
+			ProvenanceEntry { from: 1, to: 2, block: 12, kind: TransferKind::Auction, price: Some((NATIVE, 300)) },
+		]);
+	})
+}
+
+#[test]
+fn nft_lock_escrows_a_kitty() {
+	new_test_ext().execute_with(|| {
+		use nft_traits::NonFungibleAsset;
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 500))));
+		assert_eq!(Kitties::owner(0), Some(1));
+		assert_eq!(Kitties::owner(1), None);
+
+		assert_noop!(Kitties::lock(&2, 0), Error::<Test>::NotKittyOwner);
+		assert_ok!(Kitties::lock(&1, 0));
+		assert!(Kitties::is_locked(0));
+		assert_eq!(Kitties::kitty_price(0), None);
+
+		// An escrowed kitty stays with its owner but can't be used
+		assert_noop!(Kitties::lock(&1, 0), Error::<Test>::KittyEscrowed);
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyEscrowed);
+		assert_noop!(Kitties::ask(Origin::signed(1), 0, Some((NATIVE, 500))), Error::<Test>::KittyEscrowed);
+		assert_noop!(Kitties::release(Origin::signed(1), 0), Error::<Test>::KittyEscrowed);
+		assert_noop!(<Kitties as NonFungibleAsset<u64, CurrencyId, u64>>::transfer(&1, &2, 0, None), Error::<Test>::KittyEscrowed);
+
+		assert_ok!(Kitties::unlock(0));
+		assert!(!Kitties::is_locked(0));
+		assert_noop!(Kitties::unlock(0), Error::<Test>::KittyNotEscrowed);
+	})
+}
+
+#[test]
+fn nft_transfer_records_a_trade() {
+	new_test_ext().execute_with(|| {
+		use crate::{ProvenanceEntry, TransferKind};
+		use nft_traits::NonFungibleAsset;
+
+		run_to_block(10);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(
+			<Kitties as NonFungibleAsset<u64, CurrencyId, u64>>::transfer(&2, &3, 0, None),
+			Error::<Test>::NotKittyOwner
+		);
+
+		assert_ok!(<Kitties as NonFungibleAsset<u64, CurrencyId, u64>>::transfer(&1, &2, 0, Some((DOT, 300))));
+		assert_eq!(Kitties::owner(0), Some(2));
+		assert_eq!(owned_kitties(2), vec![0]);
+		assert_eq!(Balances::reserved_balance(2), 1_000_000);
+		assert_eq!(Kitties::provenance(0), vec![
+			ProvenanceEntry { from: 1, to: 2, block: 10, kind: TransferKind::Trade, price: Some((DOT, 300)) },
+		]);
+	})
+}
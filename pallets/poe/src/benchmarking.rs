@@ -0,0 +1,59 @@
+//! Benchmarks for the proof of existence pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	_ { }
+
+	create_claim {
+		let c in 0 .. T::ClaimLengthLimit::get() as u32;
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; c as usize];
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(Proofs::<T>::contains_key(&claim));
+	}
+
+	revoke_claim {
+		let c in 0 .. T::ClaimLengthLimit::get() as u32;
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = vec![0u8; c as usize];
+		Module::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!Proofs::<T>::contains_key(&claim));
+	}
+
+	transfer_claim {
+		let c in 0 .. T::ClaimLengthLimit::get() as u32;
+		let caller: T::AccountId = whitelisted_caller();
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let claim = vec![0u8; c as usize];
+		Module::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone())?;
+	}: _(RawOrigin::Signed(caller), claim.clone(), dest.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).0, dest);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_claim::<Test>());
+			assert_ok!(test_benchmark_revoke_claim::<Test>());
+			assert_ok!(test_benchmark_transfer_claim::<Test>());
+		});
+	}
+}
@@ -56,6 +56,7 @@ parameter_types! {
 impl Trait for Test {
 	type Event = ();
 	type ClaimLengthLimit = ClaimLengthLimit;
+	type WeightInfo = ();
 }
 
 pub type PoeModule = Module<Test>;
@@ -0,0 +1,46 @@
+//! Traits for non-fungible assets that other pallets can hold in escrow and trade.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::FullCodec;
+use sp_runtime::DispatchResult;
+#[cfg(feature = "runtime-benchmarks")]
+use sp_runtime::DispatchError;
+use core::fmt::Debug;
+
+/// A registry of unique assets, each owned by a single account.
+///
+/// A locked asset stays with its owner but can't change hands or be used until it is unlocked,
+/// which lets another pallet hold it in escrow without taking ownership. Assets are traded
+/// against amounts of a `CurrencyId`.
+pub trait NonFungibleAsset<AccountId, CurrencyId, Balance> {
+	/// Identifier of an asset.
+	type AssetId: FullCodec + Copy + Eq + Debug;
+
+	/// Current owner of an asset, `None` if it doesn't exist.
+	fn owner(asset_id: Self::AssetId) -> Option<AccountId>;
+
+	/// Whether the asset is locked by `lock`.
+	fn is_locked(asset_id: Self::AssetId) -> bool;
+
+	/// Lock an asset owned by `who`. Fails if `who` isn't the owner or the asset can't be
+	/// transferred right now.
+	fn lock(who: &AccountId, asset_id: Self::AssetId) -> DispatchResult;
+
+	/// Release a locked asset.
+	fn unlock(asset_id: Self::AssetId) -> DispatchResult;
+
+	/// Move an unlocked asset from `from` to `to`, for `price` if it was traded against a currency.
+	fn transfer(
+		from: &AccountId,
+		to: &AccountId,
+		asset_id: Self::AssetId,
+		price: Option<(CurrencyId, Balance)>,
+	) -> DispatchResult;
+
+	/// Create an asset owned by `owner`, for benchmarks of pallets trading assets.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn mint_for_benchmarks(_owner: &AccountId) -> Result<Self::AssetId, DispatchError> {
+		Err(DispatchError::Other("minting is not supported"))
+	}
+}
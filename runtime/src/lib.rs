@@ -65,6 +65,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Index of a kitty in the kitties pallet.
+pub type KittyIndex = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -102,7 +105,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("node-template"),
     impl_name: create_runtime_str!("node-template"),
     authoring_version: 1,
-    spec_version: 100,
+    spec_version: 101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -277,6 +280,7 @@ impl pallet_template::Trait for Runtime {
 impl pallet_poe::Trait for Runtime {
     type Event = Event;
     type ClaimLengthLimit = ClaimLengthLimit;
+    type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
 // Tyoes for orml token
@@ -314,21 +318,52 @@ impl orml_currencies::Trait for Runtime {
 
 parameter_types! {
     pub const StakeForKitty: u32 = 1_000_000;
+    pub const MaxAuctionsPerBlock: u32 = 50;
+    pub const MaxRentalsPerBlock: u32 = 50;
+    pub const BreedCooldown: BlockNumber = 10 * MINUTES;
+    pub const MutationChance: Permill = Permill::from_percent(1);
+    pub const RevealDelay: BlockNumber = MINUTES;
+    pub const RevealWindow: BlockNumber = 10 * MINUTES;
+    pub const CommitmentDeposit: Balance = 100_000;
+    pub const MaxNameLength: u32 = 32;
+    pub const MaxDescriptionLength: u32 = 256;
+    pub const MetadataDepositPerByte: Balance = 1_000;
+    pub const MaxProvenanceEntries: u32 = 100;
+    pub const MaxMatchesPerOrder: u32 = 20;
+    pub const MaxOrdersPerBook: u32 = 200;
 }
 
 impl pallet_exchange::Trait for Runtime {
     type Event = Event;
 	type Currency = Currencies;
 	type OrderId = u32;
+	type Nft = KittiesModule;
+	type MaxMatchesPerOrder = MaxMatchesPerOrder;
+	type MaxOrdersPerBook = MaxOrdersPerBook;
+	type WeightInfo = pallet_exchange::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_kitties::Trait for Runtime {
     type Event = Event;
     type Randomness = RandomnessCollectiveFlip;
     // Kitty Id
-    type KittyIndex = u32;
+    type KittyIndex = KittyIndex;
     type StakeForKitty = StakeForKitty;
+    type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+    type MaxRentalsPerBlock = MaxRentalsPerBlock;
     type Currency = pallet_balances::Module<Self>;
+    type MultiCurrency = Currencies;
+    type GetNativeCurrencyId = GetNativeCurrencyId;
+    type BreedCooldown = BreedCooldown;
+    type MutationChance = MutationChance;
+    type RevealDelay = RevealDelay;
+    type RevealWindow = RevealWindow;
+    type CommitmentDeposit = CommitmentDeposit;
+    type MaxNameLength = MaxNameLength;
+    type MaxDescriptionLength = MaxDescriptionLength;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type MaxProvenanceEntries = MaxProvenanceEntries;
+    type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -349,10 +384,10 @@ construct_runtime!(
         // Include the custom logic from the template pallet in the runtime.
         TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
         PoeModule: pallet_poe::{Module, Call, Storage, Event<T>},
-        KittiesModule: pallet_kitties::{Module, Call, Storage, Event<T>},
+        KittiesModule: pallet_kitties::{Module, Call, Storage, Event<T>, Config<T>},
         Currencies: orml_currencies::{Module, Call, Event<T>},
         Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
-        Exchange: pallet_exchange::{Module, Storage, Call, Event<T>},
+        Exchange: pallet_exchange::{Module, Storage, Call, Event<T>, Config},
     }
 );
 
@@ -516,6 +551,60 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, AccountId, KittyIndex, CurrencyId, Balance, BlockNumber> for Runtime {
+        fn kitty(kitty_id: KittyIndex) -> Option<pallet_kitties::KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>> {
+            KittiesModule::kitty_info(kitty_id)
+        }
+
+        fn kitties_of_owner(
+            owner: AccountId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<pallet_kitties::KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>> {
+            KittiesModule::kitties_of_owner(&owner, start, limit)
+        }
+
+        fn kitties_for_sale(
+            start: u32,
+            limit: u32,
+        ) -> Vec<pallet_kitties::KittyInfo<AccountId, KittyIndex, CurrencyId, Balance, BlockNumber>> {
+            KittiesModule::kitties_for_sale(start, limit)
+        }
+
+        fn dutch_auction_price(kitty_id: KittyIndex) -> Option<Balance> {
+            KittiesModule::dutch_auction_price(kitty_id)
+        }
+
+        fn phenotype(kitty_id: KittyIndex) -> Option<pallet_kitties::Phenotype> {
+            KittiesModule::phenotype(kitty_id)
+        }
+
+        fn pedigree(kitty_id: KittyIndex, depth: u32) -> Vec<pallet_kitties::PedigreeNode<KittyIndex>> {
+            KittiesModule::pedigree(kitty_id, depth)
+        }
+
+        fn common_ancestor(
+            kitty_id_1: KittyIndex,
+            kitty_id_2: KittyIndex,
+        ) -> Option<pallet_kitties::CommonAncestor<KittyIndex>> {
+            KittiesModule::common_ancestor(kitty_id_1, kitty_id_2)
+        }
+
+        fn inbreeding_coefficient(kitty_id: KittyIndex) -> Option<Perbill> {
+            KittiesModule::inbreeding_coefficient(kitty_id)
+        }
+
+        fn pairing_inbreeding_coefficient(kitty_id_1: KittyIndex, kitty_id_2: KittyIndex) -> Option<Perbill> {
+            KittiesModule::pairing_inbreeding_coefficient(kitty_id_1, kitty_id_2)
+        }
+
+        fn provenance(
+            kitty_id: KittyIndex,
+        ) -> Vec<pallet_kitties::ProvenanceEntry<AccountId, CurrencyId, Balance, BlockNumber>> {
+            KittiesModule::provenance(kitty_id)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -550,6 +639,9 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+            add_benchmark!(params, batches, pallet_kitties, KittiesModule);
+            add_benchmark!(params, batches, pallet_poe, PoeModule);
+            add_benchmark!(params, batches, pallet_exchange, Exchange);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
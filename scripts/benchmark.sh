@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+
+set -e
+
+echo "*** Building the node with benchmarks ***"
+
+cargo build --release --manifest-path node/Cargo.toml --features runtime-benchmarks
+
+out=$(mktemp -d)
+for pallet in kitties poe exchange; do
+    echo "*** Benchmarking pallet_$pallet ***"
+    (cd $out && $OLDPWD/target/release/node-template benchmark \
+        --chain dev --execution wasm --wasm-execution compiled \
+        --pallet pallet_$pallet --extrinsic '*' --steps 50 --repeat 20 \
+        --output --weight-trait)
+    mv $out/pallet_$pallet.rs pallets/$pallet/src/weights.rs
+done
+rm -r $out